@@ -0,0 +1,150 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use boba::DecodeError;
+
+/// The two-pass decoder that predates the single-pass table-driven decoder,
+/// kept as a throughput baseline. It validates the alphabet in a separate scan
+/// before decoding.
+fn decode_two_pass(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    const fn alphabet_table() -> [bool; 256] {
+        let alphabet = b"aeiouybcdfghklmnprstvzx-";
+        let mut table = [false; 256];
+        let mut idx = 0;
+        while idx < alphabet.len() {
+            table[alphabet[idx] as usize] = true;
+            idx += 1;
+        }
+        table
+    }
+
+    static ALPHABET_TABLE: [bool; 256] = alphabet_table();
+
+    fn index_from_vowel(vowel: u8) -> Option<u8> {
+        let index = match vowel {
+            b'a' => 0,
+            b'e' => 1,
+            b'i' => 2,
+            b'o' => 3,
+            b'u' => 4,
+            b'y' => 5,
+            _ => return None,
+        };
+        Some(index)
+    }
+
+    fn index_from_consonant(consonant: u8) -> Option<u8> {
+        let index = match consonant {
+            b'b' => 0,
+            b'c' => 1,
+            b'd' => 2,
+            b'f' => 3,
+            b'g' => 4,
+            b'h' => 5,
+            b'k' => 6,
+            b'l' => 7,
+            b'm' => 8,
+            b'n' => 9,
+            b'p' => 10,
+            b'r' => 11,
+            b's' => 12,
+            b't' => 13,
+            b'v' => 14,
+            b'z' => 15,
+            _ => return None,
+        };
+        Some(index)
+    }
+
+    fn decode_3_tuple(byte1: u8, byte2: u8, byte3: u8, checksum: u8) -> Result<u8, DecodeError> {
+        let high = (byte1 + 6 - (checksum % 6)) % 6;
+        let low = (byte3 + 6 - ((checksum / 6) % 6)) % 6;
+        if high >= 4 || low >= 4 {
+            Err(DecodeError::Corrupted)
+        } else {
+            Ok((high << 6) | (byte2 << 2) | low)
+        }
+    }
+
+    if encoded == b"xexax" {
+        return Ok(Vec::new());
+    }
+    let enc = match encoded {
+        [b'x', enc @ .., b'x'] => enc,
+        [b'x', ..] => return Err(DecodeError::MalformedTrailer),
+        [.., b'x'] => return Err(DecodeError::MalformedHeader),
+        _ => return Err(DecodeError::Corrupted),
+    };
+    if let Some((_, pos)) = enc
+        .iter()
+        .zip(1_usize..)
+        .find(|(&byte, _)| !ALPHABET_TABLE[usize::from(byte)])
+    {
+        return Err(DecodeError::InvalidByte(pos));
+    }
+    let mut decoded = Vec::with_capacity(2 * ((encoded.len() + 1) / 6));
+    let mut checksum = 1_u8;
+    let mut chunks = enc.chunks_exact(6);
+    while let Some(&[left, mid, right, up, b'-', down]) = chunks.next() {
+        let byte1 = decode_3_tuple(
+            index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?,
+            index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
+            index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?,
+            checksum,
+        )?;
+        let byte2 = (index_from_consonant(up).ok_or(DecodeError::ExpectedConsonant)? << 4)
+            | index_from_consonant(down).ok_or(DecodeError::ExpectedConsonant)?;
+        checksum =
+            ((u16::from(checksum * 5) + (u16::from(byte1) * 7) + u16::from(byte2)) % 36) as u8;
+        decoded.push(byte1);
+        decoded.push(byte2);
+    }
+    match *chunks.remainder() {
+        [left, b'x', right] => {
+            let a = index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?;
+            let c = index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?;
+            if a != checksum % 6 || c != checksum / 6 {
+                return Err(DecodeError::ChecksumMismatch);
+            }
+            Ok(decoded)
+        }
+        [left, mid, right] => {
+            let byte = decode_3_tuple(
+                index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?,
+                index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
+                index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?,
+                checksum,
+            )?;
+            decoded.push(byte);
+            Ok(decoded)
+        }
+        _ => Err(DecodeError::Corrupted),
+    }
+}
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for &len in &[32, 1024, 1024 * 1024, 16 * 1024 * 1024] {
+        let data = (0..len).map(|i| (i * 31 + i / 7) as u8).collect::<Vec<_>>();
+        let encoded = boba::encode(&data);
+        assert_eq!(decode_two_pass(encoded.as_bytes()).unwrap(), data);
+
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_with_input(BenchmarkId::new("table", len), &encoded, |b, encoded| {
+            b.iter(|| boba::decode(black_box(encoded)));
+        });
+        group.bench_with_input(BenchmarkId::new("two_pass", len), &encoded, |b, encoded| {
+            b.iter(|| decode_two_pass(black_box(encoded.as_bytes())));
+        });
+        let mut decoded = Vec::with_capacity(len);
+        group.bench_with_input(BenchmarkId::new("into", len), &encoded, |b, encoded| {
+            b.iter(|| {
+                decoded.clear();
+                boba::decode_into(&mut decoded, black_box(encoded))
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,100 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const VOWELS: [char; 6] = ['a', 'e', 'i', 'o', 'u', 'y'];
+const CONSONANTS: [char; 16] = [
+    'b', 'c', 'd', 'f', 'g', 'h', 'k', 'l', 'm', 'n', 'p', 'r', 's', 't', 'v', 'z',
+];
+
+/// The character-at-a-time encoder that predates the table-driven encoder,
+/// kept as a throughput baseline.
+fn encode_chars(data: &[u8]) -> String {
+    fn odd_partial(byte: u8, checksum: u8, buf: &mut String) {
+        buf.push(VOWELS[usize::from((((byte >> 6) & 3) + checksum) % 6)]);
+        buf.push(CONSONANTS[usize::from((byte >> 2) & 15)]);
+        buf.push(VOWELS[usize::from(((byte & 3) + checksum / 6) % 6)]);
+    }
+
+    let mut encoded = String::with_capacity(6 * (data.len() / 2) + 5);
+    encoded.push('x');
+    let mut checksum = 1_u8;
+    let mut chunks = data.chunks_exact(2);
+    for pair in &mut chunks {
+        let (left, right) = (pair[0], pair[1]);
+        odd_partial(left, checksum, &mut encoded);
+        encoded.push(CONSONANTS[usize::from((right >> 4) & 15)]);
+        encoded.push('-');
+        encoded.push(CONSONANTS[usize::from(right & 15)]);
+        checksum = ((u16::from(checksum * 5) + u16::from(left) * 7 + u16::from(right)) % 36) as u8;
+    }
+    if let [byte] = *chunks.remainder() {
+        odd_partial(byte, checksum, &mut encoded);
+    } else {
+        encoded.push(VOWELS[usize::from(checksum % 6)]);
+        encoded.push('x');
+        encoded.push(VOWELS[usize::from(checksum / 6)]);
+    }
+    encoded.push('x');
+    encoded
+}
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for &len in &[32, 1024, 1024 * 1024] {
+        let data = (0..len).map(|i| (i * 31 + i / 7) as u8).collect::<Vec<_>>();
+        assert_eq!(encode_chars(&data), boba::encode(&data));
+
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("table", len), &data, |b, data| {
+            b.iter(|| boba::encode(black_box(data)));
+        });
+        group.bench_with_input(BenchmarkId::new("chars", len), &data, |b, data| {
+            b.iter(|| encode_chars(black_box(data)));
+        });
+        let mut buf = vec![0; boba::encoded_len(len)];
+        group.bench_with_input(BenchmarkId::new("slice", len), &data, |b, data| {
+            b.iter(|| boba::encode_to_slice(black_box(data), &mut buf));
+        });
+    }
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    let hashes = (0..1000_u32)
+        .map(|i| {
+            let mut hash = [0; 32];
+            hash[..4].copy_from_slice(&i.to_le_bytes());
+            hash
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Bytes(32 * hashes.len() as u64));
+    group.bench_function("encode", |b| {
+        b.iter(|| {
+            hashes
+                .iter()
+                .map(|hash| boba::encode(black_box(hash)))
+                .collect::<Vec<_>>()
+        });
+    });
+    let mut encoded = String::new();
+    group.bench_function("encode_into", |b| {
+        b.iter(|| {
+            for hash in &hashes {
+                encoded.clear();
+                boba::encode_into(&mut encoded, black_box(hash));
+            }
+        });
+    });
+    let mut batch = boba::BatchEncoder::with_capacity(hashes.len(), 32);
+    group.bench_function("batch_encoder", |b| {
+        b.iter(|| {
+            batch.clear();
+            batch.extend(black_box(&hashes));
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, encode, batch);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+fn par(c: &mut Criterion) {
+    let one_thread = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("par_encode");
+    for &len in &[1024 * 1024, 16 * 1024 * 1024] {
+        let data = (0..len).map(|i| (i * 31 + i / 7) as u8).collect::<Vec<_>>();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", len), &data, |b, data| {
+            b.iter(|| boba::encode(black_box(data)));
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", len), &data, |b, data| {
+            b.iter(|| boba::par_encode(black_box(data)));
+        });
+        group.bench_with_input(BenchmarkId::new("1_thread", len), &data, |b, data| {
+            b.iter(|| one_thread.install(|| boba::par_encode(black_box(data))));
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("par_decode");
+    for &len in &[1024 * 1024, 16 * 1024 * 1024] {
+        let data = (0..len).map(|i| (i * 31 + i / 7) as u8).collect::<Vec<_>>();
+        let encoded = boba::encode(&data);
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("sequential", len),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| boba::decode(black_box(encoded)));
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("parallel", len), &encoded, |b, encoded| {
+            b.iter(|| boba::par_decode(black_box(encoded)));
+        });
+        group.bench_with_input(BenchmarkId::new("1_thread", len), &encoded, |b, encoded| {
+            b.iter(|| one_thread.install(|| boba::par_decode(black_box(encoded))));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, par);
+criterion_main!(benches);
@@ -0,0 +1,204 @@
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use boba::transcode::Format;
+use boba::DecodeError;
+use serde::Serialize;
+
+use crate::codec;
+use crate::error::Error;
+
+/// The result of decoding one line of input in batch mode.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    /// 1-based line number.
+    line: u64,
+    /// The line as read, excluding the line terminator.
+    input: &'a str,
+    /// Lowercase hex encoding of the decoded bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorReport>,
+}
+
+/// A structured [`DecodeError`].
+#[derive(Debug, Serialize)]
+struct ErrorReport {
+    /// The name of the `DecodeError` variant.
+    kind: &'static str,
+    /// The offset of the invalid byte in the line for
+    /// [`DecodeError::InvalidByte`] errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<usize>,
+    message: String,
+}
+
+impl From<DecodeError> for ErrorReport {
+    fn from(err: DecodeError) -> Self {
+        let position = match err {
+            DecodeError::InvalidByte(pos) => Some(pos),
+            _ => None,
+        };
+        Self {
+            kind: kind(err),
+            position,
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Counts of decoded lines, emitted as the final record in batch mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    /// Number of non-blank lines.
+    pub total: u64,
+    /// Number of lines that decoded successfully.
+    pub decoded: u64,
+    /// Number of lines that failed to decode.
+    pub failed: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct SummaryRecord {
+    summary: Summary,
+}
+
+fn kind(err: DecodeError) -> &'static str {
+    match err {
+        DecodeError::ChecksumMismatch => "ChecksumMismatch",
+        DecodeError::Corrupted => "Corrupted",
+        DecodeError::ExpectedConsonant => "ExpectedConsonant",
+        DecodeError::ExpectedVowel => "ExpectedVowel",
+        DecodeError::InvalidByte(_) => "InvalidByte",
+        DecodeError::MalformedHeader => "MalformedHeader",
+        DecodeError::MalformedTrailer => "MalformedTrailer",
+    }
+}
+
+/// Decode each line of `input` and write a JSON Lines report to `out`.
+///
+/// Input is read one line at a time so memory use is bounded by the longest
+/// line. Blank lines are skipped but still counted for line numbers. A final
+/// record with a [`Summary`] is written after all lines are decoded.
+///
+/// `input_path` and `output_path` are used to attribute I/O errors.
+pub fn run<R, W>(
+    input: &mut R,
+    input_path: Option<&Path>,
+    lenient: bool,
+    out: &mut W,
+    output_path: Option<&Path>,
+) -> Result<Summary, Error>
+where
+    R: BufRead + ?Sized,
+    W: Write + ?Sized,
+{
+    let read_error = |err| Error::io(input_path.map(Path::to_owned), err);
+    let write_error = |err| Error::io(output_path.map(Path::to_owned), err);
+
+    let mut summary = Summary::default();
+    let mut buf = Vec::new();
+    let mut line = 0_u64;
+    loop {
+        buf.clear();
+        if input.read_until(b'\n', &mut buf).map_err(read_error)? == 0 {
+            break;
+        }
+        line += 1;
+        let encoded = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let encoded = encoded.strip_suffix(b"\r").unwrap_or(encoded);
+        if encoded.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        summary.total += 1;
+        let mut record = Record {
+            line,
+            input: &String::from_utf8_lossy(encoded),
+            hex: None,
+            error: None,
+        };
+        match codec::decode(encoded, lenient) {
+            Ok(decoded) => {
+                summary.decoded += 1;
+                record.hex = Some(Format::Hex.encode(decoded));
+            }
+            Err(err) => {
+                summary.failed += 1;
+                record.error = Some(err.into());
+            }
+        }
+        write_record(out, &record).map_err(write_error)?;
+    }
+    write_record(out, &SummaryRecord { summary }).map_err(write_error)?;
+    Ok(summary)
+}
+
+fn write_record<W, T>(out: &mut W, record: &T) -> std::io::Result<()>
+where
+    W: Write + ?Sized,
+    T: Serialize,
+{
+    serde_json::to_writer(&mut *out, record)?;
+    out.write_all(b"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, Summary};
+
+    fn batch(input: &str, lenient: bool) -> (String, Summary) {
+        let mut out = Vec::new();
+        let summary = run(&mut input.as_bytes(), None, lenient, &mut out, None).unwrap();
+        (String::from_utf8(out).unwrap(), summary)
+    }
+
+    #[test]
+    fn report() {
+        let input = "xigak-nyryk-humil-bosek-sonax\r\n\nxigak-nyryk-Humil-bosek-sonax\nxexax";
+        let (out, summary) = batch(input, false);
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                r#"{"line":1,"input":"xigak-nyryk-humil-bosek-sonax","hex":"50696e656170706c65"}"#,
+                r#"{"line":3,"input":"xigak-nyryk-Humil-bosek-sonax","error":{"kind":"InvalidByte","position":12,"message":"Encountered byte outside of encoding alphabet at position 12"}}"#,
+                r#"{"line":4,"input":"xexax","hex":""}"#,
+                r#"{"summary":{"total":3,"decoded":2,"failed":1}}"#,
+            ]
+        );
+        assert_eq!(
+            summary,
+            Summary {
+                total: 3,
+                decoded: 2,
+                failed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let (out, summary) = batch("  XEXAX \n", true);
+        assert!(out.starts_with(r#"{"line":1,"input":"  XEXAX ","hex":""}"#));
+        assert_eq!(summary.failed, 0);
+    }
+
+    #[test]
+    fn error_without_position() {
+        let (out, _) = batch("xesefxdisofxgytufxkatofxmovifxbaxux\n", false);
+        assert!(out.starts_with(
+            r#"{"line":1,"input":"xesefxdisofxgytufxkatofxmovifxbaxux","error":{"kind":"ChecksumMismatch","message":"Checksum mismatch"}}"#
+        ));
+    }
+
+    #[test]
+    fn empty_input() {
+        let (out, summary) = batch("", false);
+        assert_eq!(
+            out,
+            "{\"summary\":{\"total\":0,\"decoded\":0,\"failed\":0}}\n"
+        );
+        assert_eq!(summary, Summary::default());
+    }
+}
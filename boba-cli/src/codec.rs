@@ -0,0 +1,183 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use boba::DecodeError;
+
+use crate::progress::Progress;
+
+/// Encoded output is produced and written in blocks of about this many bytes.
+const BLOCK_SIZE: usize = 8 << 20;
+
+/// Encode `data` and write it to `out` followed by a newline, inserting a line
+/// break every `wrap` characters. A `wrap` of 0 disables line wrapping.
+///
+/// The input is encoded one block at a time into a single reused output
+/// buffer of about [`BLOCK_SIZE`] bytes, which is written to `out` before the
+/// next block is encoded. Progress is reported to `progress` after each block.
+pub fn encode<W: Write + ?Sized>(
+    data: &[u8],
+    wrap: usize,
+    out: &mut W,
+    progress: Option<&mut Progress>,
+) -> io::Result<()> {
+    // Every pair of input bytes encodes to 6 output bytes.
+    encode_blocks(data, BLOCK_SIZE / 3, wrap, out, progress)
+}
+
+fn encode_blocks<W: Write + ?Sized>(
+    data: &[u8],
+    block_len: usize,
+    wrap: usize,
+    out: &mut W,
+    mut progress: Option<&mut Progress>,
+) -> io::Result<()> {
+    let mut stream = boba::Encoder::new();
+    let mut encoded = String::with_capacity(block_len * 3 + 7);
+    let mut column = 0;
+    let mut consumed = 0;
+    for block in data.chunks(block_len.max(1)) {
+        encoded.clear();
+        stream.update(&mut encoded, block);
+        write_wrapped(out, encoded.as_bytes(), wrap, &mut column)?;
+        consumed += block.len();
+        if let Some(progress) = progress.as_mut() {
+            progress.update(consumed);
+        }
+    }
+    encoded.clear();
+    stream.finish(&mut encoded);
+    write_wrapped(out, encoded.as_bytes(), wrap, &mut column)?;
+    if wrap == 0 || column > 0 {
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Write `bytes` to `out`, inserting a line break every `wrap` characters.
+///
+/// `column` is the number of characters already written to the current line,
+/// which carries line wrapping across calls.
+fn write_wrapped<W: Write + ?Sized>(
+    out: &mut W,
+    mut bytes: &[u8],
+    wrap: usize,
+    column: &mut usize,
+) -> io::Result<()> {
+    if wrap == 0 {
+        return out.write_all(bytes);
+    }
+    while !bytes.is_empty() {
+        let (line, rest) = bytes.split_at((wrap - *column).min(bytes.len()));
+        out.write_all(line)?;
+        *column += line.len();
+        if *column == wrap {
+            out.write_all(b"\n")?;
+            *column = 0;
+        }
+        bytes = rest;
+    }
+    Ok(())
+}
+
+/// Decode `input`.
+///
+/// Line breaks are always ignored so wrapped output from [`encode`] decodes.
+/// In lenient mode, all ASCII whitespace is ignored and uppercase letters are
+/// folded to lowercase.
+///
+/// Positions in returned [`DecodeError::InvalidByte`] errors are offsets into
+/// `input`.
+pub fn decode(input: &[u8], lenient: bool) -> Result<Vec<u8>, DecodeError> {
+    let normalized = normalize(input, lenient);
+    boba::decode(&normalized).map_err(|err| match err {
+        DecodeError::InvalidByte(pos) => {
+            DecodeError::InvalidByte(original_position(input, lenient, pos))
+        }
+        err => err,
+    })
+}
+
+fn is_ignored(byte: u8, lenient: bool) -> bool {
+    if lenient {
+        byte.is_ascii_whitespace()
+    } else {
+        byte == b'\n' || byte == b'\r'
+    }
+}
+
+fn normalize(input: &[u8], lenient: bool) -> Cow<'_, [u8]> {
+    let needs_normalization = input
+        .iter()
+        .any(|&byte| is_ignored(byte, lenient) || (lenient && byte.is_ascii_uppercase()));
+    if !needs_normalization {
+        return Cow::Borrowed(input);
+    }
+    let normalized = input
+        .iter()
+        .filter(|&&byte| !is_ignored(byte, lenient))
+        .map(|&byte| {
+            if lenient {
+                byte.to_ascii_lowercase()
+            } else {
+                byte
+            }
+        })
+        .collect();
+    Cow::Owned(normalized)
+}
+
+/// Map an offset into the normalized input back to an offset into `input`.
+fn original_position(input: &[u8], lenient: bool, pos: usize) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .filter(|&(_, &byte)| !is_ignored(byte, lenient))
+        .nth(pos)
+        .map_or(input.len(), |(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use boba::DecodeError;
+
+    use super::{decode, encode, encode_blocks};
+
+    #[test]
+    fn blocks_match_single_pass() {
+        let data = (0..=255_u8).cycle().take(1000).collect::<Vec<_>>();
+        for wrap in [0, 1, 6, 12, 80] {
+            let mut expected = Vec::new();
+            encode(&data, wrap, &mut expected, None).unwrap();
+            for block_len in [1, 2, 3, 7, 64, 999, 1000, 4096] {
+                let mut out = Vec::new();
+                encode_blocks(&data, block_len, wrap, &mut out, None).unwrap();
+                assert_eq!(out, expected, "wrap {wrap} in blocks of {block_len}");
+            }
+        }
+        let mut out = Vec::new();
+        encode(&data, 0, &mut out, None).unwrap();
+        assert_eq!(out, format!("{}\n", boba::encode(&data)).as_bytes());
+    }
+
+    #[test]
+    fn wrap() {
+        let mut out = Vec::new();
+        encode(b"Pineapple", 12, &mut out, None).unwrap();
+        assert_eq!(out, b"xigak-nyryk-\nhumil-bosek-\nsonax\n");
+        assert_eq!(decode(&out, false).unwrap(), b"Pineapple");
+    }
+
+    #[test]
+    fn lenient() {
+        let input = b" XIGAK-NYRYK-HUMIL-BOSEK-SONAX \n";
+        assert_eq!(decode(input, false), Err(DecodeError::Corrupted));
+        assert_eq!(decode(input, true).unwrap(), b"Pineapple");
+    }
+
+    #[test]
+    fn invalid_byte_position_in_original_input() {
+        let input = b"xigak-\nnyryk-\r\n/umil-bosek-sonax\n";
+        assert_eq!(decode(input, false), Err(DecodeError::InvalidByte(15)));
+        assert_eq!(input[15], b'/');
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use boba::transcode::TranscodeError;
+use boba::DecodeError;
+
+/// Exit status when an input or output file could not be read or written.
+pub const EXIT_IO: u8 = 1;
+/// Exit status when one or more lines failed to decode in batch mode.
+pub const EXIT_BATCH_FAILURES: u8 = 3;
+/// Exit status when `boba scan` found no encodings.
+pub const EXIT_NO_MATCHES: u8 = 4;
+/// Exit status for each variant of [`DecodeError`].
+pub const EXIT_CHECKSUM_MISMATCH: u8 = 10;
+pub const EXIT_CORRUPTED: u8 = 11;
+pub const EXIT_EXPECTED_CONSONANT: u8 = 12;
+pub const EXIT_EXPECTED_VOWEL: u8 = 13;
+pub const EXIT_INVALID_BYTE: u8 = 14;
+pub const EXIT_MALFORMED_HEADER: u8 = 15;
+pub const EXIT_MALFORMED_TRAILER: u8 = 16;
+/// Exit status when `boba transcode` input is not valid hex.
+pub const EXIT_INVALID_HEX: u8 = 17;
+/// Exit status when `boba transcode` input is not valid base64.
+pub const EXIT_INVALID_BASE64: u8 = 18;
+
+/// Help text describing the exit statuses of `boba`.
+pub const EXIT_STATUS_HELP: &str = "\
+Exit status:
+  0   Success
+  1   I/O error reading input or writing output
+  2   Invalid command line arguments
+  3   Batch mode: one or more lines failed to decode
+  4   Scan: no encodings found
+  10  Decode error: checksum mismatch
+  11  Decode error: corrupted input
+  12  Decode error: expected consonant
+  13  Decode error: expected vowel
+  14  Decode error: byte outside of encoding alphabet
+  15  Decode error: missing 'x' header
+  16  Decode error: missing 'x' trailer
+  17  Transcode error: invalid hex
+  18  Transcode error: invalid base64";
+
+/// Errors that terminate `boba`.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing `path`, or standard input or output if `path` is
+    /// `None`, failed.
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// The input failed to decode.
+    Decode(DecodeError),
+    /// The given number of lines failed to decode in batch mode.
+    BatchFailures(u64),
+    /// `boba scan` found no encodings.
+    NoMatches,
+    /// The input to `boba transcode` failed to decode.
+    Transcode(TranscodeError),
+}
+
+impl Error {
+    /// Wrap an I/O error that occurred while accessing `path`.
+    pub fn io(path: Option<PathBuf>, source: io::Error) -> Self {
+        Self::Io { path, source }
+    }
+
+    /// The process exit status that reports this error.
+    pub fn exit_code(&self) -> ExitCode {
+        let code = match self {
+            Self::Io { .. } => EXIT_IO,
+            Self::BatchFailures(_) => EXIT_BATCH_FAILURES,
+            Self::NoMatches => EXIT_NO_MATCHES,
+            Self::Decode(err) | Self::Transcode(TranscodeError::InvalidBubbleBabble(err)) => {
+                decode_exit_code(*err)
+            }
+            Self::Transcode(TranscodeError::InvalidHex) => EXIT_INVALID_HEX,
+            Self::Transcode(TranscodeError::InvalidBase64) => EXIT_INVALID_BASE64,
+        };
+        ExitCode::from(code)
+    }
+}
+
+fn decode_exit_code(err: DecodeError) -> u8 {
+    match err {
+        DecodeError::ChecksumMismatch => EXIT_CHECKSUM_MISMATCH,
+        DecodeError::Corrupted => EXIT_CORRUPTED,
+        DecodeError::ExpectedConsonant => EXIT_EXPECTED_CONSONANT,
+        DecodeError::ExpectedVowel => EXIT_EXPECTED_VOWEL,
+        DecodeError::InvalidByte(_) => EXIT_INVALID_BYTE,
+        DecodeError::MalformedHeader => EXIT_MALFORMED_HEADER,
+        DecodeError::MalformedTrailer => EXIT_MALFORMED_TRAILER,
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "{}: {}", path.display(), source),
+            Self::Io { path: None, source } => write!(f, "{source}"),
+            Self::Decode(err) => write!(f, "invalid input: {err}"),
+            Self::BatchFailures(1) => f.write_str("1 line failed to decode"),
+            Self::BatchFailures(count) => write!(f, "{count} lines failed to decode"),
+            Self::NoMatches => f.write_str("no Bubble Babble encodings found"),
+            Self::Transcode(err) => write!(f, "invalid input: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Decode(err) => Some(err),
+            Self::BatchFailures(_) | Self::NoMatches => None,
+            Self::Transcode(err) => Some(err),
+        }
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<TranscodeError> for Error {
+    fn from(err: TranscodeError) -> Self {
+        Self::Transcode(err)
+    }
+}
@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::error::Error;
+
+/// The contents of an input file or standard input.
+///
+/// Regular files are memory-mapped so multi-gigabyte inputs are paged in by
+/// the kernel instead of being copied into the heap.
+#[derive(Debug)]
+pub enum Input {
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(map) => map,
+            Self::Buffered(buf) => buf,
+        }
+    }
+}
+
+impl Input {
+    /// Read all input from `path`, or from standard input if `path` is `None`
+    /// or `-`.
+    ///
+    /// If `output` names the same file as `path`, the file is read into memory
+    /// instead of being mapped, since creating the output truncates it.
+    pub fn read(path: Option<&Path>, output: Option<&Path>) -> Result<Self, Error> {
+        match path {
+            Some(path) if path != Path::new("-") => {
+                let overwritten = output.is_some_and(|output| same_file(path, output));
+                Self::read_file(path, !overwritten)
+                    .map_err(|err| Error::io(Some(path.to_owned()), err))
+            }
+            _ => {
+                let mut input = Vec::new();
+                io::stdin()
+                    .lock()
+                    .read_to_end(&mut input)
+                    .map_err(|err| Error::io(None, err))?;
+                Ok(Self::Buffered(input))
+            }
+        }
+    }
+
+    fn read_file(path: &Path, mappable: bool) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let metadata = file.metadata()?;
+        // Empty files cannot be mapped, and special files like pipes and
+        // character devices do not have a meaningful length.
+        if mappable && metadata.is_file() && metadata.len() > 0 {
+            if let Ok(map) = map(&file) {
+                return Ok(Self::Mapped(map));
+            }
+        }
+        let mut input = Vec::new();
+        file.read_to_end(&mut input)?;
+        Ok(Self::Buffered(input))
+    }
+}
+
+/// Whether `input` and `output` name the same existing file.
+pub fn same_file(input: &Path, output: &Path) -> bool {
+    match (input.canonicalize(), output.canonicalize()) {
+        (Ok(input), Ok(output)) => input == output,
+        _ => false,
+    }
+}
+
+#[allow(unsafe_code)]
+fn map(file: &File) -> io::Result<Mmap> {
+    // SAFETY: The map is only read from. If another process truncates or
+    // modifies the file while it is mapped, `boba` may read inconsistent
+    // data or be terminated with `SIGBUS`, the same hazards as any tool that
+    // maps its input. `Input::read` does not map a file that is also the
+    // output, so `boba` never truncates a file it has mapped.
+    unsafe { Mmap::map(file) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::Input;
+
+    #[test]
+    fn maps_regular_files() {
+        let dir = std::env::temp_dir().join(format!("boba-cli-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.bin");
+
+        fs::write(&path, b"Pineapple").unwrap();
+        let input = Input::read(Some(&path), None).unwrap();
+        assert!(matches!(input, Input::Mapped(_)));
+        assert_eq!(&*input, b"Pineapple");
+
+        let input = Input::read(Some(&path), Some(&dir.join(".").join("input.bin"))).unwrap();
+        assert!(matches!(input, Input::Buffered(_)));
+        assert_eq!(&*input, b"Pineapple");
+
+        fs::write(&path, b"").unwrap();
+        let input = Input::read(Some(&path), None).unwrap();
+        assert!(matches!(input, Input::Buffered(_)));
+        assert!(input.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
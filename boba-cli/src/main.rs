@@ -0,0 +1,182 @@
+#![warn(clippy::all)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::cargo)]
+#![allow(unknown_lints)]
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(rust_2018_idioms)]
+#![warn(trivial_casts, trivial_numeric_casts)]
+#![warn(unused_qualifications)]
+#![warn(variant_size_differences)]
+#![deny(unsafe_code)]
+
+//! `boba` encodes and decodes data with the Bubble Babble binary data
+//! encoding.
+//!
+//! ```text
+//! $ echo -n Pineapple | boba
+//! xigak-nyryk-humil-bosek-sonax
+//! $ echo xigak-nyryk-humil-bosek-sonax | boba --decode
+//! Pineapple
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+mod batch;
+mod codec;
+mod error;
+mod input;
+mod progress;
+mod repl;
+mod scan;
+mod transcode;
+
+use error::Error;
+use input::Input;
+use progress::Progress;
+
+/// Encode or decode data with the Bubble Babble encoding.
+///
+/// With no FILE, or when FILE is -, read standard input.
+#[derive(Debug, Parser)]
+#[command(
+    name = "boba",
+    version,
+    after_help = error::EXIT_STATUS_HELP,
+    args_conflicts_with_subcommands = true
+)]
+// Each flag is an independent command line switch.
+#[allow(clippy::struct_excessive_bools)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Decode data.
+    #[arg(short, long, group = "decoding")]
+    decode: bool,
+    /// Decode one encoding per line and write a JSON Lines report with the
+    /// decoded hex or decode error for each line, followed by summary counts.
+    #[arg(short, long, group = "decoding")]
+    batch: bool,
+    /// Wrap encoded lines after COLS characters. Use 0 to disable line
+    /// wrapping.
+    #[arg(
+        short,
+        long,
+        value_name = "COLS",
+        default_value_t = 0,
+        conflicts_with = "decoding"
+    )]
+    wrap: usize,
+    /// Report encoding progress and throughput on standard error.
+    #[arg(long, conflicts_with = "decoding")]
+    progress: bool,
+    /// When decoding, ignore all whitespace and accept uppercase letters.
+    #[arg(short, long, requires = "decoding")]
+    lenient: bool,
+    /// Write output to FILE instead of standard output.
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// The file to read input from.
+    #[arg(value_name = "FILE")]
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    Repl(repl::Args),
+    #[command(after_help = error::EXIT_STATUS_HELP)]
+    Scan(scan::Args),
+    #[command(after_help = error::EXIT_STATUS_HELP)]
+    Transcode(transcode::Args),
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("boba: {err}");
+            err.exit_code()
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    match &args.command {
+        Some(Command::Repl(args)) => return repl::run(args),
+        Some(Command::Scan(args)) => return scan::run(args),
+        Some(Command::Transcode(args)) => return transcode::run(args),
+        None => {}
+    }
+    let output = args.output.as_deref();
+    let write_error = |err| Error::io(output.map(Path::to_owned), err);
+    if args.batch {
+        let input = args.file.as_deref().filter(|&path| path != Path::new("-"));
+        let mut reader = open_input(input, output)?;
+        let summary = with_output(output, |out| {
+            batch::run(&mut reader, input, args.lenient, out, output)
+        })?;
+        if summary.failed > 0 {
+            return Err(Error::BatchFailures(summary.failed));
+        }
+        return Ok(());
+    }
+    let input = Input::read(args.file.as_deref(), output)?;
+    if args.decode {
+        // Decode before creating the output file so invalid input does not
+        // truncate an existing file.
+        let decoded = codec::decode(&input, args.lenient)?;
+        return with_output(output, |out| out.write_all(&decoded).map_err(write_error));
+    }
+    with_output(output, |out| {
+        let mut progress = args.progress.then(|| Progress::new(input.len()));
+        codec::encode(&input, args.wrap, out, progress.as_mut()).map_err(write_error)?;
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+        Ok(())
+    })
+}
+
+/// Open a buffered reader for `path`, or for standard input if `path` is
+/// `None`.
+///
+/// If `output` names the same file as `path`, the file is read into memory
+/// up front, since creating the output truncates it.
+fn open_input(path: Option<&Path>, output: Option<&Path>) -> Result<Box<dyn BufRead>, Error> {
+    match path {
+        Some(path) => {
+            let attach = |err| Error::io(Some(path.to_owned()), err);
+            let mut file = File::open(path).map_err(attach)?;
+            if output.is_some_and(|output| input::same_file(path, output)) {
+                let mut contents = Vec::new();
+                file.read_to_end(&mut contents).map_err(attach)?;
+                return Ok(Box::new(io::Cursor::new(contents)));
+            }
+            Ok(Box::new(BufReader::new(file)))
+        }
+        None => Ok(Box::new(io::stdin().lock())),
+    }
+}
+
+/// Run `f` with a buffered writer to `path`, or to standard output if `path`
+/// is `None`, and flush the writer.
+fn with_output<F, T>(path: Option<&Path>, f: F) -> Result<T, Error>
+where
+    F: FnOnce(&mut dyn Write) -> Result<T, Error>,
+{
+    let attach = |err| Error::io(path.map(Path::to_owned), err);
+    let sink: Box<dyn Write> = match path {
+        Some(path) => Box::new(File::create(path).map_err(attach)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(sink);
+    let value = f(&mut out)?;
+    out.flush().map_err(attach)?;
+    Ok(value)
+}
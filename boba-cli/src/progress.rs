@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Reports encoding progress and throughput to standard error.
+#[derive(Debug)]
+pub struct Progress {
+    total: u64,
+    start: Instant,
+    last_report: Option<Instant>,
+}
+
+impl Progress {
+    /// Start tracking progress through `total` bytes of input.
+    pub fn new(total: usize) -> Self {
+        Self {
+            total: total as u64,
+            start: Instant::now(),
+            last_report: None,
+        }
+    }
+
+    /// Record that `processed` bytes of input have been encoded. Reports are
+    /// rate limited.
+    pub fn update(&mut self, processed: usize) {
+        let now = Instant::now();
+        if self
+            .last_report
+            .is_some_and(|last| now.duration_since(last) < REPORT_INTERVAL)
+        {
+            return;
+        }
+        self.last_report = Some(now);
+        let processed = processed as u64;
+        let percent = (processed * 100).checked_div(self.total).unwrap_or(100);
+        let _ = write!(
+            io::stderr().lock(),
+            "\rboba: {} / {} ({}%), {}/s\x1b[K",
+            format_bytes(processed),
+            format_bytes(self.total),
+            percent,
+            format_bytes(self.rate(processed, now)),
+        );
+    }
+
+    /// Report the final throughput.
+    pub fn finish(&self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.start);
+        let _ = writeln!(
+            io::stderr().lock(),
+            "\rboba: encoded {} in {:.2}s, {}/s\x1b[K",
+            format_bytes(self.total),
+            elapsed.as_secs_f64(),
+            format_bytes(self.rate(self.total, now)),
+        );
+    }
+
+    // Throughput is only displayed, so precision loss is acceptable.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn rate(&self, processed: u64, now: Instant) -> u64 {
+        let elapsed = now.duration_since(self.start).as_secs_f64();
+        if elapsed > 0.0 {
+            (processed as f64 / elapsed) as u64
+        } else {
+            0
+        }
+    }
+}
+
+/// Format a byte count with binary units.
+// Byte counts are only displayed, so precision loss is acceptable.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+    }
+}
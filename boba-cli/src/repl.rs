@@ -0,0 +1,407 @@
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::io;
+
+use boba::transcode::Format;
+use boba::DecodeError;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::{Completer, Context, Editor, Helper, Validator};
+
+use crate::error::Error;
+
+const VOWELS: &[u8] = b"aeiouy";
+const CONSONANTS: &[u8] = b"bcdfghklmnprstvz";
+const MAX_SUGGESTIONS: usize = 5;
+
+const INVALID_STYLE: &str = "\x1b[1;31m";
+const HINT_STYLE: &str = "\x1b[2m";
+const RESET_STYLE: &str = "\x1b[0m";
+
+/// Decode Bubble Babble interactively.
+///
+/// While typing, the word being entered and the first invalid character are
+/// shown. Press Enter to decode the line and print the decoded bytes as hex
+/// and UTF-8. If the checksum does not match, likely corrections are
+/// suggested. Words may be separated by dashes, spaces, or nothing at all, and
+/// uppercase letters are accepted. Press Ctrl-D to exit.
+#[derive(Debug, clap::Args)]
+pub struct Args {}
+
+pub fn run(_args: &Args) -> Result<(), Error> {
+    let mut editor =
+        Editor::<ReplHelper, DefaultHistory>::new().map_err(|err| Error::io(None, into_io(err)))?;
+    editor.set_helper(Some(ReplHelper));
+    loop {
+        match editor.readline("boba> ") {
+            Ok(line) if line.trim().is_empty() => {}
+            Ok(line) => {
+                let _ = editor.add_history_entry(line.as_str());
+                print!("{}", report(&line));
+            }
+            Err(ReadlineError::Interrupted) => {}
+            Err(ReadlineError::Eof) => return Ok(()),
+            Err(err) => return Err(Error::io(None, into_io(err))),
+        }
+    }
+}
+
+fn into_io(err: ReadlineError) -> io::Error {
+    match err {
+        ReadlineError::Io(err) => err,
+        err => io::Error::other(err),
+    }
+}
+
+#[derive(Completer, Helper, Validator)]
+struct ReplHelper;
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        // Only hint when the cursor is at the end of the line so the hint
+        // does not split the input.
+        if pos < line.len() || line.trim().is_empty() {
+            return None;
+        }
+        Some(format!("  [{}]", Analysis::new(line).status(line)))
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        match Analysis::new(line).invalid {
+            Some(idx) => {
+                let len = line[idx..].chars().next().map_or(0, char::len_utf8);
+                let (head, tail) = line.split_at(idx);
+                let (invalid, tail) = tail.split_at(len);
+                Cow::Owned(format!("{head}{INVALID_STYLE}{invalid}{RESET_STYLE}{tail}"))
+            }
+            None => Cow::Borrowed(line),
+        }
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(format!("{HINT_STYLE}{hint}{RESET_STYLE}"))
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, _forced: bool) -> bool {
+        true
+    }
+}
+
+/// Validation state of a partially entered encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Analysis {
+    /// The encoding with letters folded to lowercase and `-` separators
+    /// between words.
+    normalized: Vec<u8>,
+    /// Byte offset in the line of each byte in `normalized`.
+    offsets: Vec<usize>,
+    /// Byte offset in the line of the first character that cannot appear at
+    /// its position in a valid encoding.
+    invalid: Option<usize>,
+    /// The result of decoding the line once the trailing `x` is entered.
+    decoded: Option<Result<Vec<u8>, DecodeError>>,
+}
+
+impl Analysis {
+    fn new(line: &str) -> Self {
+        let mut normalized = Vec::with_capacity(line.len());
+        let mut offsets = Vec::with_capacity(line.len());
+        let letters = line
+            .bytes()
+            .enumerate()
+            .filter(|&(_, byte)| !byte.is_ascii_whitespace() && byte != b'-');
+        for (count, (idx, byte)) in letters.enumerate() {
+            // Separators are inserted at their canonical positions, so
+            // words may be separated by spaces or not separated at all.
+            if count >= 5 && count % 5 == 0 {
+                normalized.push(b'-');
+                offsets.push(idx);
+            }
+            normalized.push(byte.to_ascii_lowercase());
+            offsets.push(idx);
+        }
+        let (invalid, decoded) = match check_prefix(&normalized) {
+            Err(idx) => (Some(offsets[idx]), None),
+            Ok(false) => (None, None),
+            Ok(true) => (None, Some(boba::decode(&normalized))),
+        };
+        Self {
+            normalized,
+            offsets,
+            invalid,
+            decoded,
+        }
+    }
+
+    /// The 1-based index of the dash-separated word being entered and its
+    /// text so far.
+    fn word(&self) -> (usize, &str) {
+        let (index, word) = self
+            .normalized
+            .split(|&byte| byte == b'-')
+            .enumerate()
+            .last()
+            .unwrap_or((0, &[]));
+        (index + 1, std::str::from_utf8(word).unwrap_or_default())
+    }
+
+    /// A one-line summary of `line` for the live hint.
+    fn status(&self, line: &str) -> String {
+        if let Some(idx) = self.invalid {
+            return format!("unexpected character at column {}", column(line, idx) + 1);
+        }
+        match &self.decoded {
+            Some(Ok(decoded)) => format!("ok: {} bytes", decoded.len()),
+            Some(Err(err)) => format!("error: {err}"),
+            None => {
+                let (index, word) = self.word();
+                format!("word {index}: {word}")
+            }
+        }
+    }
+}
+
+/// The 0-based column in `line` of the character at byte offset `idx`.
+fn column(line: &str, idx: usize) -> usize {
+    line[..idx].chars().count()
+}
+
+/// Check that `encoded` is a prefix of a well-formed encoding.
+///
+/// Returns `Ok(true)` if the trailing `x` has been entered, `Ok(false)` if
+/// more characters are needed, and `Err` with the index of the first byte
+/// that cannot appear at its position.
+fn check_prefix(encoded: &[u8]) -> Result<bool, usize> {
+    let is_vowel = |byte| VOWELS.contains(&byte);
+    let is_consonant = |byte| CONSONANTS.contains(&byte);
+    match encoded.first() {
+        None => return Ok(false),
+        Some(b'x') => {}
+        Some(_) => return Err(0),
+    }
+    // Set once the `x` checksum marker of an even-length final tuple is seen.
+    let mut final_tuple = false;
+    for (idx, &byte) in encoded.iter().enumerate().skip(1) {
+        let valid = match (idx - 1) % 6 {
+            0 | 2 => is_vowel(byte),
+            1 if byte == b'x' => {
+                final_tuple = true;
+                true
+            }
+            1 | 5 => is_consonant(byte),
+            3 => byte == b'x' || (!final_tuple && is_consonant(byte)),
+            _ => byte == b'-',
+        };
+        if !valid {
+            return Err(idx);
+        }
+        if (idx - 1) % 6 == 3 && byte == b'x' {
+            return if idx + 1 == encoded.len() {
+                Ok(true)
+            } else {
+                Err(idx + 1)
+            };
+        }
+    }
+    Ok(false)
+}
+
+/// Encodings one edit away from `encoded` that decode successfully.
+///
+/// Transposed letters are tried before single character substitutions.
+/// Vowels and consonants alternate, so only letters two positions apart are
+/// swapped, which keeps each letter in a position of its own class.
+fn suggestions(encoded: &[u8]) -> Vec<String> {
+    // The header and trailer are fixed, so only edit the bytes between them.
+    let editable = 1..encoded.len().saturating_sub(1);
+    let class = |byte: u8| {
+        if VOWELS.contains(&byte) {
+            Some(VOWELS)
+        } else if CONSONANTS.contains(&byte) {
+            Some(CONSONANTS)
+        } else {
+            None
+        }
+    };
+
+    let mut found = Vec::new();
+    let mut consider = |candidate: &[u8]| {
+        if found.len() < MAX_SUGGESTIONS && candidate != encoded && boba::decode(candidate).is_ok()
+        {
+            let candidate = String::from_utf8_lossy(candidate).into_owned();
+            if !found.contains(&candidate) {
+                found.push(candidate);
+            }
+        }
+    };
+    let mut candidate = encoded.to_vec();
+    for idx in editable.clone() {
+        let other = idx + 2;
+        if editable.contains(&other)
+            && class(encoded[idx]).is_some()
+            && class(encoded[idx]) == class(encoded[other])
+        {
+            candidate.swap(idx, other);
+            consider(&candidate);
+            candidate.swap(idx, other);
+        }
+    }
+    for idx in editable {
+        let original = encoded[idx];
+        if let Some(alphabet) = class(original) {
+            for &replacement in alphabet {
+                candidate[idx] = replacement;
+                consider(&candidate);
+            }
+            candidate[idx] = original;
+        }
+    }
+    found
+}
+
+/// The report printed after a line is entered.
+fn report(line: &str) -> String {
+    let analysis = Analysis::new(line);
+    let mut report = String::new();
+    let caret = |report: &mut String, idx: usize| {
+        let _ = writeln!(report, "  {}", line.trim_end());
+        let _ = writeln!(report, "  {:>width$}", "^", width = column(line, idx) + 1);
+    };
+    if let Some(idx) = analysis.invalid {
+        report.push_str("error: unexpected character\n");
+        caret(&mut report, idx);
+        return report;
+    }
+    match analysis.decoded {
+        None => report.push_str("error: incomplete encoding, expected a trailing 'x'\n"),
+        Some(Ok(decoded)) => {
+            let _ = writeln!(report, "hex:   {}", Format::Hex.encode(&decoded));
+            match std::str::from_utf8(&decoded) {
+                Ok(text) => {
+                    let _ = writeln!(report, "utf-8: {text:?}");
+                }
+                Err(_) => report.push_str("utf-8: (not valid UTF-8)\n"),
+            }
+        }
+        Some(Err(err)) => {
+            let _ = writeln!(report, "error: {err}");
+            if let DecodeError::InvalidByte(pos) = err {
+                caret(&mut report, analysis.offsets[pos]);
+            }
+            if matches!(err, DecodeError::ChecksumMismatch | DecodeError::Corrupted) {
+                let suggestions = suggestions(&analysis.normalized);
+                if !suggestions.is_empty() {
+                    report.push_str("did you mean:\n");
+                    for suggestion in suggestions {
+                        let _ = writeln!(report, "  {suggestion}");
+                    }
+                }
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_prefix, report, suggestions, Analysis};
+
+    #[test]
+    fn prefixes() {
+        let encoded = b"xigak-nyryk-humil-bosek-sonax";
+        for len in 0..encoded.len() {
+            assert_eq!(check_prefix(&encoded[..len]), Ok(false));
+        }
+        assert_eq!(check_prefix(encoded), Ok(true));
+        assert_eq!(check_prefix(b"xexax"), Ok(true));
+        assert_eq!(check_prefix(b"xexaxx"), Err(5));
+        assert_eq!(check_prefix(b"xigqk"), Err(3));
+        assert_eq!(check_prefix(b"xexab"), Err(4));
+        assert_eq!(check_prefix(b"xigak_"), Err(5));
+        assert_eq!(check_prefix(b"bexax"), Err(0));
+    }
+
+    #[test]
+    fn invalid_offset_in_line() {
+        let line = " XIGAK NYRqK";
+        let analysis = Analysis::new(line);
+        assert_eq!(analysis.invalid, Some(10));
+        assert_eq!(analysis.status(line), "unexpected character at column 11");
+    }
+
+    #[test]
+    fn invalid_column_with_leading_whitespace() {
+        let line = "	  xigak  nyrqk";
+        let analysis = Analysis::new(line);
+        assert_eq!(analysis.invalid, Some(13));
+        assert_eq!(analysis.status(line), "unexpected character at column 14");
+        assert_eq!(
+            report(line),
+            "error: unexpected character
+  	  xigak  nyrqk
+               ^
+"
+        );
+    }
+
+    #[test]
+    fn current_word() {
+        let analysis = Analysis::new("xigak-nyryk-hu");
+        assert_eq!(analysis.status("xigak-nyryk-hu"), "word 3: hu");
+        assert_eq!(Analysis::new("xexax").status("xexax"), "ok: 0 bytes");
+        assert_eq!(
+            Analysis::new("xexex").status("xexex"),
+            "error: Checksum mismatch"
+        );
+    }
+
+    #[test]
+    fn decoded_report() {
+        assert_eq!(
+            report("XIGAK NYRYK HUMIL BOSEK SONAX"),
+            "hex:   50696e656170706c65\nutf-8: \"Pineapple\"\n"
+        );
+        assert_eq!(
+            report("xuvoz-zexax"),
+            "hex:   fbff\nutf-8: (not valid UTF-8)\n"
+        );
+    }
+
+    #[test]
+    fn error_report_points_at_character() {
+        assert_eq!(
+            report("xigak-nyrqk"),
+            "error: unexpected character\n  xigak-nyrqk\n           ^\n"
+        );
+    }
+
+    #[test]
+    fn suggests_corrections() {
+        let suggestions = suggestions(b"xesef-disof-gytuf-katof-movif-baxox");
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.len() <= 5);
+        for suggestion in &suggestions {
+            assert!(boba::decode(suggestion).is_ok());
+        }
+        let checksum_mismatch = report("xesef-disof-gytuf-katof-movif-baxox");
+        assert_eq!(
+            checksum_mismatch,
+            "error: Checksum mismatch\ndid you mean:\n  xesef-disof-gytuf-katof-movif-baxux\n"
+        );
+        let corrupted = report("xigak-nyryk-humil-bosek-sonox");
+        assert!(corrupted.starts_with("error: Corrupted input\ndid you mean:\n"));
+    }
+
+    #[test]
+    fn suggests_transposition() {
+        // Swap two adjacent vowels in a valid encoding.
+        let suggestions = suggestions(b"xesef-dosif-gytuf-katof-movif-baxux");
+        assert!(suggestions.contains(&String::from("xesef-disof-gytuf-katof-movif-baxux")));
+    }
+}
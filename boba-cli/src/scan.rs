@@ -0,0 +1,130 @@
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use boba::transcode::Format;
+
+use crate::error::Error;
+use crate::input::same_file;
+
+/// Find Bubble Babble encodings in text.
+///
+/// Print each valid encoding with its line number, column, and decoded bytes
+/// as hex. When searching more than one file, prefix each match with the file
+/// name. With no FILE, or when FILE is -, read standard input.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Write output to FILE instead of standard output.
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// The files to search.
+    #[arg(value_name = "FILE")]
+    files: Vec<PathBuf>,
+}
+
+pub fn run(args: &Args) -> Result<(), Error> {
+    let output = args.output.as_deref();
+    let with_filename = args.files.len() > 1;
+    let inputs = if args.files.is_empty() {
+        vec![None]
+    } else {
+        args.files
+            .iter()
+            .map(|path| Some(path.as_path()).filter(|&path| path != Path::new("-")))
+            .collect()
+    };
+    // Creating the output truncates it, so read any input that is also the
+    // output first.
+    let preread = inputs
+        .iter()
+        .map(|&input| match (input, output) {
+            (Some(path), Some(output)) if same_file(path, output) => {
+                crate::open_input(input, Some(output)).map(Some)
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let found = crate::with_output(output, |out| {
+        let mut found = 0_u64;
+        for (input, preread) in inputs.into_iter().zip(preread) {
+            let mut reader = match preread {
+                Some(reader) => reader,
+                None => crate::open_input(input, None)?,
+            };
+            let filename = with_filename.then(|| {
+                input.map_or_else(
+                    || String::from("(standard input)"),
+                    |path| path.display().to_string(),
+                )
+            });
+            found += scan(&mut reader, input, filename.as_deref(), out, output)?;
+        }
+        Ok(found)
+    })?;
+    if found == 0 {
+        return Err(Error::NoMatches);
+    }
+    Ok(())
+}
+
+/// Scan `input` line by line and write one annotated line per match to `out`,
+/// returning the number of matches.
+fn scan<R, W>(
+    input: &mut R,
+    input_path: Option<&Path>,
+    filename: Option<&str>,
+    out: &mut W,
+    output_path: Option<&Path>,
+) -> Result<u64, Error>
+where
+    R: BufRead + ?Sized,
+    W: Write + ?Sized,
+{
+    let read_error = |err| Error::io(input_path.map(Path::to_owned), err);
+    let write_error = |err| Error::io(output_path.map(Path::to_owned), err);
+
+    let mut found = 0;
+    let mut buf = Vec::new();
+    let mut line = 0_u64;
+    loop {
+        buf.clear();
+        if input.read_until(b'\n', &mut buf).map_err(read_error)? == 0 {
+            break;
+        }
+        line += 1;
+        for m in boba::scan(&buf) {
+            found += 1;
+            if let Some(filename) = filename {
+                write!(out, "{filename}:").map_err(write_error)?;
+            }
+            writeln!(
+                out,
+                "{line}:{column}:{encoded} {hex}",
+                column = m.start() + 1,
+                encoded = String::from_utf8_lossy(m.encoded()),
+                hex = Format::Hex.encode(m.decoded()),
+            )
+            .map_err(write_error)?;
+        }
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scan;
+
+    #[test]
+    fn annotates_matches() {
+        let text = "no encodings here\n\
+                    key=xigak-nyryk-humil-bosek-sonax, empty=xexax\n\
+                    bad=xigak-nyryk-humil-bosek-sonox\n";
+        let mut out = Vec::new();
+        let found = scan(&mut text.as_bytes(), None, Some("log"), &mut out, None).unwrap();
+        assert_eq!(found, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "log:2:5:xigak-nyryk-humil-bosek-sonax 50696e656170706c65\n\
+             log:2:42:xexax \n"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use boba::transcode::{Format, TranscodeError};
+use boba::DecodeError;
+use clap::ValueEnum;
+
+use crate::error::Error;
+use crate::input::Input;
+
+/// Formats accepted by `boba transcode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FormatArg {
+    /// Hexadecimal digits.
+    Hex,
+    /// Base64 with the standard alphabet and padding.
+    Base64,
+    /// Base64 with the URL-safe alphabet and no padding.
+    #[value(name = "base64url")]
+    Base64Url,
+    /// Bubble Babble.
+    #[value(name = "bubblebabble", alias = "bb")]
+    BubbleBabble,
+}
+
+impl From<FormatArg> for Format {
+    fn from(format: FormatArg) -> Self {
+        match format {
+            FormatArg::Hex => Self::Hex,
+            FormatArg::Base64 => Self::Base64,
+            FormatArg::Base64Url => Self::Base64Url,
+            FormatArg::BubbleBabble => Self::BubbleBabble,
+        }
+    }
+}
+
+/// Convert data between hex, base64, and Bubble Babble.
+///
+/// Leading and trailing whitespace in the input is ignored. Output is
+/// terminated with a newline. With no FILE, or when FILE is -, read standard
+/// input.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The format of the input.
+    #[arg(short, long, value_name = "FORMAT")]
+    from: FormatArg,
+    /// The format to convert the input to.
+    #[arg(short, long, value_name = "FORMAT")]
+    to: FormatArg,
+    /// Write output to FILE instead of standard output.
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// The file to read input from.
+    #[arg(value_name = "FILE")]
+    file: Option<PathBuf>,
+}
+
+pub fn run(args: &Args) -> Result<(), Error> {
+    let output = args.output.as_deref();
+    let write_error = |err| Error::io(output.map(Path::to_owned), err);
+    let input = Input::read(args.file.as_deref(), output)?;
+    let (from, to) = (args.from.into(), args.to.into());
+    // Validate before creating the output file so invalid input does not
+    // truncate an existing file.
+    let (offset, input) = trim(&input);
+    validate(input, offset, from)?;
+    crate::with_output(output, |out| {
+        boba::transcode::transcode_with(input, from, to, |encoded| {
+            out.write_all(encoded.as_bytes()).map_err(write_error)
+        })?;
+        writeln!(out).map_err(write_error)
+    })
+}
+
+/// Strip leading and trailing whitespace from `input`, returning the offset
+/// of the remaining input and the input.
+fn trim(input: &[u8]) -> (usize, &[u8]) {
+    let start = input
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(input.len());
+    let end = input
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |idx| idx + 1);
+    (start, &input[start..end])
+}
+
+/// Check that `input`, found at `offset` in the original input, is valid in
+/// the `from` format.
+///
+/// Positions in returned [`DecodeError::InvalidByte`] errors are offsets into
+/// the original input.
+fn validate(input: &[u8], offset: usize, from: Format) -> Result<(), Error> {
+    from.validate(input).map_err(|err| match err {
+        TranscodeError::InvalidBubbleBabble(DecodeError::InvalidByte(pos)) => {
+            TranscodeError::InvalidBubbleBabble(DecodeError::InvalidByte(offset + pos))
+        }
+        err => err,
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use boba::transcode::{Format, TranscodeError};
+    use boba::DecodeError;
+
+    use super::{trim, validate};
+    use crate::error::Error;
+
+    #[test]
+    fn trims_whitespace() {
+        assert_eq!(
+            trim(b"  50696e656170706c65\r\n"),
+            (2, &b"50696e656170706c65"[..])
+        );
+        assert_eq!(trim(b" \n"), (2, &b""[..]));
+    }
+
+    #[test]
+    fn error() {
+        let err = validate(b"5069 6e65", 0, Format::Hex).unwrap_err();
+        assert!(matches!(err, Error::Transcode(TranscodeError::InvalidHex)));
+    }
+
+    #[test]
+    fn invalid_byte_position_in_original_input() {
+        let input = b"\n  xigak-nyryk-Humil-bosek-sonax\n";
+        let (offset, trimmed) = trim(input);
+        let err = validate(trimmed, offset, Format::BubbleBabble).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Transcode(TranscodeError::InvalidBubbleBabble(
+                DecodeError::InvalidByte(15)
+            ))
+        ));
+        assert_eq!(input[15], b'H');
+    }
+}
@@ -0,0 +1,345 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn boba(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_boba"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn encode_stdin() {
+    let output = boba(&[], b"Pineapple");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"xigak-nyryk-humil-bosek-sonax\n");
+}
+
+#[test]
+fn encode_wrap() {
+    let output = boba(&["--wrap", "12"], b"Pineapple");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"xigak-nyryk-\nhumil-bosek-\nsonax\n");
+}
+
+#[test]
+fn decode_stdin() {
+    let output = boba(&["-d", "-"], b"xigak-nyryk-\nhumil-bosek-\nsonax\n");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"Pineapple");
+}
+
+#[test]
+fn decode_lenient() {
+    let input = b"  XIGAK-NYRYK-HUMIL-BOSEK-SONAX\n";
+    let output = boba(&["--decode"], input);
+    assert_eq!(output.status.code(), Some(11));
+
+    let output = boba(&["--decode", "--lenient"], input);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"Pineapple");
+}
+
+#[test]
+fn file_input_and_output() {
+    let dir = std::env::temp_dir().join(format!("boba-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.txt");
+    let output = dir.join("output.bin");
+    std::fs::write(&input, "xesef-disof-gytuf-katof-movif-baxux\n").unwrap();
+
+    let status = boba(
+        &[
+            "-d",
+            "-o",
+            output.to_str().unwrap(),
+            input.to_str().unwrap(),
+        ],
+        b"",
+    );
+    assert!(status.status.success());
+    assert!(status.stdout.is_empty());
+    assert_eq!(std::fs::read(&output).unwrap(), b"1234567890");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn invalid_input_does_not_clobber_output() {
+    let dir = std::env::temp_dir().join(format!("boba-cli-clobber-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("output.bin");
+    std::fs::write(&output, "keep me").unwrap();
+
+    let status = boba(
+        &["-d", "-o", output.to_str().unwrap()],
+        b"xigak-nyryk-Humil-bosek-sonax\n",
+    );
+    assert_eq!(status.status.code(), Some(14));
+    assert_eq!(std::fs::read(&output).unwrap(), b"keep me");
+
+    let status = boba(
+        &[
+            "transcode",
+            "-f",
+            "bb",
+            "-t",
+            "hex",
+            "-o",
+            output.to_str().unwrap(),
+        ],
+        b"xexa\n",
+    );
+    assert_eq!(status.status.code(), Some(16));
+    assert_eq!(std::fs::read(&output).unwrap(), b"keep me");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn output_overwrites_input() {
+    let dir = std::env::temp_dir().join(format!("boba-cli-in-place-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data");
+    std::fs::write(&path, "Pineapple").unwrap();
+
+    let status = boba(&["-o", path.to_str().unwrap(), path.to_str().unwrap()], b"");
+    assert!(status.status.success());
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        b"xigak-nyryk-humil-bosek-sonax\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_file() {
+    let output = boba(&["this/file/does/not/exist"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("boba: this/file/does/not/exist: "));
+}
+
+#[test]
+fn exit_codes() {
+    let cases: &[(&[u8], i32)] = &[
+        (b"xesefxdisofxgytufxkatofxmovifxbaxux", 10),
+        (b"xax", 11),
+        (b"xeeef-disof-gytuf-katof-movif-baxux", 12),
+        (b"xssef-disof-gytuf-katof-movif-baxux", 13),
+        (b"xigak-nyryk-humil-bosek-sonax", 0),
+        (b"xigak-nyryk-Humil-bosek-sonax", 14),
+        (b"aigak-nyryk-humil-bosek-sonax", 15),
+        (b"xigak-nyryk-humil-bosek-sonaa", 16),
+    ];
+    for &(input, code) in cases {
+        let output = boba(&["-d"], input);
+        assert_eq!(
+            output.status.code(),
+            Some(code),
+            "{}",
+            String::from_utf8_lossy(input)
+        );
+    }
+}
+
+#[test]
+fn invalid_byte_position_in_original_input() {
+    let output = boba(&["-d"], b"xigak-\nnyryk-\nHumil-bosek-sonax\n");
+    assert_eq!(output.status.code(), Some(14));
+    assert_eq!(
+        output.stderr,
+        b"boba: invalid input: Encountered byte outside of encoding alphabet at position 14\n"
+    );
+}
+
+#[test]
+fn usage_error() {
+    let output = boba(&["--decode", "--wrap", "10"], b"");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn batch() {
+    let input = b"xigak-nyryk-humil-bosek-sonax\nxexax\n";
+    let output = boba(&["--batch"], input);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"line\":1,\"input\":\"xigak-nyryk-humil-bosek-sonax\",\"hex\":\"50696e656170706c65\"}\n\
+         {\"line\":2,\"input\":\"xexax\",\"hex\":\"\"}\n\
+         {\"summary\":{\"total\":2,\"decoded\":2,\"failed\":0}}\n"
+    );
+}
+
+#[test]
+fn batch_failures() {
+    let input = b"xexax\nxigak-nyryk-Humil-bosek-sonax\nxax\n";
+    let output = boba(&["--batch"], input);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stderr, b"boba: 2 lines failed to decode\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().last(),
+        Some(r#"{"summary":{"total":3,"decoded":1,"failed":2}}"#)
+    );
+}
+
+#[test]
+fn batch_output_overwrites_input() {
+    let dir = std::env::temp_dir().join(format!("boba-cli-batch-in-place-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data");
+    std::fs::write(&path, "xexax\n").unwrap();
+
+    let status = boba(
+        &[
+            "--batch",
+            "-o",
+            path.to_str().unwrap(),
+            path.to_str().unwrap(),
+        ],
+        b"",
+    );
+    assert!(status.status.success());
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "{\"line\":1,\"input\":\"xexax\",\"hex\":\"\"}\n\
+         {\"summary\":{\"total\":1,\"decoded\":1,\"failed\":0}}\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn batch_conflicts_with_decode() {
+    let output = boba(&["--batch", "--decode"], b"");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn transcode() {
+    let output = boba(
+        &["transcode", "--from", "hex", "--to", "bb"],
+        b"50696e656170706c65\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"xigak-nyryk-humil-bosek-sonax\n");
+
+    let output = boba(
+        &["transcode", "-f", "bubblebabble", "-t", "base64url"],
+        b"xuvoz-zexax\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"-_8\n");
+}
+
+#[test]
+fn transcode_errors() {
+    let output = boba(&["transcode", "-f", "hex", "-t", "base64"], b"abc");
+    assert_eq!(output.status.code(), Some(17));
+    let output = boba(&["transcode", "-f", "base64", "-t", "hex"], b"-_8");
+    assert_eq!(output.status.code(), Some(18));
+    let output = boba(&["transcode", "-f", "bb", "-t", "hex"], b"xexa");
+    assert_eq!(output.status.code(), Some(16));
+    let output = boba(&["transcode", "-f", "hex"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    let output = boba(
+        &["transcode", "-f", "bb", "-t", "hex"],
+        b"\n  xigak-nyryk-Humil-bosek-sonax\n",
+    );
+    assert_eq!(output.status.code(), Some(14));
+    assert_eq!(
+        output.stderr,
+        b"boba: invalid input: Input is not valid Bubble Babble: \
+          Encountered byte outside of encoding alphabet at position 15\n"
+    );
+}
+
+#[test]
+fn scan() {
+    let input = b"accepted xigak-nyryk-humil-bosek-sonax\nrejected xigak-nyryk-humil-bosek-sonox\n";
+    let output = boba(&["scan"], input);
+    assert!(output.status.success());
+    assert_eq!(
+        output.stdout,
+        b"1:10:xigak-nyryk-humil-bosek-sonax 50696e656170706c65\n"
+    );
+
+    let output = boba(&["scan", "-", "-"], input);
+    assert!(output.status.success());
+    assert_eq!(
+        output.stdout,
+        b"(standard input):1:10:xigak-nyryk-humil-bosek-sonax 50696e656170706c65\n"
+    );
+}
+
+#[test]
+fn scan_output_overwrites_input() {
+    let dir = std::env::temp_dir().join(format!("boba-cli-scan-in-place-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data");
+    std::fs::write(&path, "key xexax\n").unwrap();
+
+    let status = boba(
+        &["scan", "-o", path.to_str().unwrap(), path.to_str().unwrap()],
+        b"",
+    );
+    assert!(status.status.success());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "1:5:xexax \n");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn scan_no_matches() {
+    let output = boba(&["scan"], b"nothing to see here\n");
+    assert_eq!(output.status.code(), Some(4));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn repl() {
+    let input = b"XIGAK NYRYK HUMIL BOSEK SONAX\n\nxigak-nyrqk\n";
+    let output = boba(&["repl"], input);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "hex:   50696e656170706c65\n\
+         utf-8: \"Pineapple\"\n\
+         error: unexpected character\n  \
+         xigak-nyrqk\n           ^\n"
+    );
+}
+
+#[test]
+fn encode_large_file_with_progress() {
+    let dir = std::env::temp_dir().join(format!("boba-cli-large-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.bin");
+    let data = (0..(3 << 20) + 1)
+        .map(|idx: u32| idx.to_le_bytes()[0] ^ idx.to_le_bytes()[2])
+        .collect::<Vec<u8>>();
+    std::fs::write(&input, &data).unwrap();
+
+    let output = boba(&["--progress", input.to_str().unwrap()], b"");
+    assert!(output.status.success());
+    let mut expected = boba::encode(&data).into_bytes();
+    expected.push(b'\n');
+    assert!(output.stdout == expected);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("boba: encoded 3.0 MiB in "));
+
+    let output = boba(&["--wrap", "80", input.to_str().unwrap()], b"");
+    assert!(output.status.success());
+    let output = boba(&["--decode"], &output.stdout);
+    assert!(output.stdout == data);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,215 @@
+//! Decode Bubble Babble literals with the same rules as `boba::decode`.
+//!
+//! `boba` depends on this crate to re-export its macros, so this crate cannot
+//! depend on `boba` for its decoder. This is a straightforward port of it, and
+//! the tests check that both return the same result, including which
+//! `DecodeError` is returned for invalid input.
+
+use std::fmt;
+
+const VOWELS: [u8; 6] = *b"aeiouy";
+const CONSONANTS: [u8; 16] = *b"bcdfghklmnprstvz";
+const HEADER: u8 = b'x';
+const TRAILER: u8 = b'x';
+
+/// Mirrors `boba::DecodeError`, including its `Debug` and `Display` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    ChecksumMismatch,
+    Corrupted,
+    ExpectedConsonant,
+    ExpectedVowel,
+    InvalidByte(usize),
+    MalformedHeader,
+    MalformedTrailer,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChecksumMismatch => f.write_str("Checksum mismatch"),
+            Self::Corrupted => f.write_str("Corrupted input"),
+            Self::ExpectedConsonant => f.write_str("Expected consonant, got something else"),
+            Self::ExpectedVowel => f.write_str("Expected vowel, got something else"),
+            Self::InvalidByte(pos) => write!(
+                f,
+                "Encountered byte outside of encoding alphabet at position {}",
+                pos
+            ),
+            Self::MalformedHeader => f.write_str("Missing required 'x' header"),
+            Self::MalformedTrailer => f.write_str("Missing required 'x' trailer"),
+        }
+    }
+}
+
+pub fn decode(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    // `xexax` is the encoded representation of an empty byte string.
+    if encoded == b"xexax" {
+        return Ok(Vec::new());
+    }
+    let enc = match encoded {
+        [HEADER, enc @ .., TRAILER] => enc,
+        [HEADER, ..] => return Err(DecodeError::MalformedTrailer),
+        [.., TRAILER] => return Err(DecodeError::MalformedHeader),
+        _ => return Err(DecodeError::Corrupted),
+    };
+    // Bytes outside of the encoding alphabet take precedence over structural
+    // errors.
+    decode_body(enc).map_err(|err| invalid_byte(enc).unwrap_or(err))
+}
+
+fn decode_body(enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::with_capacity(enc.len() / 3 + 1);
+    let mut checksum = 1_u8;
+    let mut chunks = enc.chunks_exact(6);
+    for chunk in &mut chunks {
+        let (left, mid, right, up, down) = match *chunk {
+            [left, mid, right, up, b'-', down] => (left, mid, right, up, down),
+            _ if enc.len() % 6 == 3 => return Err(DecodeError::ChecksumMismatch),
+            _ => return Err(DecodeError::Corrupted),
+        };
+        let byte1 = decode_3_tuple(
+            vowel(left).ok_or(DecodeError::ExpectedVowel)?,
+            consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
+            vowel(right).ok_or(DecodeError::ExpectedVowel)?,
+            checksum,
+        )?;
+        let up = consonant(up).ok_or(DecodeError::ExpectedConsonant)?;
+        let down = consonant(down).ok_or(DecodeError::ExpectedConsonant)?;
+        let byte2 = (up << 4) | down;
+        checksum = ((u16::from(checksum) * 5 + u16::from(byte1) * 7 + u16::from(byte2)) % 36) as u8;
+        decoded.push(byte1);
+        decoded.push(byte2);
+    }
+    let (left, mid, right) = match *chunks.remainder() {
+        [left, mid, right] => (left, mid, right),
+        _ => return Err(DecodeError::Corrupted),
+    };
+    let a = vowel(left).ok_or(DecodeError::ExpectedVowel)?;
+    let c = vowel(right).ok_or(DecodeError::ExpectedVowel)?;
+    if mid == b'x' {
+        if a != checksum % 6 || c != checksum / 6 {
+            return Err(DecodeError::ChecksumMismatch);
+        }
+    } else {
+        let b = consonant(mid).ok_or(DecodeError::ExpectedConsonant)?;
+        decoded.push(decode_3_tuple(a, b, c, checksum)?);
+    }
+    Ok(decoded)
+}
+
+/// Find the first byte outside of the encoding alphabet.
+fn invalid_byte(enc: &[u8]) -> Option<DecodeError> {
+    enc.iter()
+        // start `pos` at 1 because we stripped off a leading 'x'
+        .zip(1..)
+        .find(|&(&byte, _)| {
+            vowel(byte).is_none() && consonant(byte).is_none() && byte != b'x' && byte != b'-'
+        })
+        .map(|(_, pos)| DecodeError::InvalidByte(pos))
+}
+
+fn decode_3_tuple(a: u8, b: u8, c: u8, checksum: u8) -> Result<u8, DecodeError> {
+    let high = (a + 6 - (checksum % 6)) % 6;
+    let low = (c + 6 - ((checksum / 6) % 6)) % 6;
+    if high >= 4 || low >= 4 {
+        Err(DecodeError::Corrupted)
+    } else {
+        Ok((high << 6) | (b << 2) | low)
+    }
+}
+
+fn vowel(byte: u8) -> Option<u8> {
+    VOWELS
+        .iter()
+        .position(|&vowel| vowel == byte)
+        .map(|index| index as u8)
+}
+
+fn consonant(byte: u8) -> Option<u8> {
+    CONSONANTS
+        .iter()
+        .position(|&consonant| consonant == byte)
+        .map(|index| index as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use boba::DecodeError as BobaError;
+
+    use super::{decode, DecodeError};
+
+    fn assert_matches_boba(encoded: &[u8]) {
+        let expected = boba::decode(encoded).map_err(|err| format!("{:?}", err));
+        let actual = decode(encoded).map_err(|err| format!("{:?}", err));
+        assert_eq!(
+            actual,
+            expected,
+            "decoding {:?}",
+            String::from_utf8_lossy(encoded)
+        );
+    }
+
+    #[test]
+    fn display_matches_boba() {
+        let errors = [
+            (DecodeError::ChecksumMismatch, BobaError::ChecksumMismatch),
+            (DecodeError::Corrupted, BobaError::Corrupted),
+            (DecodeError::ExpectedConsonant, BobaError::ExpectedConsonant),
+            (DecodeError::ExpectedVowel, BobaError::ExpectedVowel),
+            (DecodeError::InvalidByte(12), BobaError::InvalidByte(12)),
+            (DecodeError::MalformedHeader, BobaError::MalformedHeader),
+            (DecodeError::MalformedTrailer, BobaError::MalformedTrailer),
+        ];
+        for (actual, expected) in errors {
+            assert_eq!(actual.to_string(), expected.to_string());
+            assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
+        }
+    }
+
+    #[test]
+    fn matches_boba_on_valid_input() {
+        let inputs = (0..=255_u8).collect::<Vec<_>>();
+        for len in 0..64 {
+            assert_matches_boba(boba::encode(&inputs[..len]).as_bytes());
+            assert_matches_boba(boba::encode(&inputs[len..len * 2]).as_bytes());
+        }
+    }
+
+    #[test]
+    fn matches_boba_on_every_single_byte_mutation() {
+        for data in [&b""[..], b"a", b"Pineapple", b"1234567890"] {
+            let encoded = boba::encode(data).into_bytes();
+            for pos in 0..encoded.len() {
+                for byte in 0..=255_u8 {
+                    let mut mutated = encoded.clone();
+                    mutated[pos] = byte;
+                    assert_matches_boba(&mutated);
+                }
+                assert_matches_boba(&encoded[..pos]);
+                assert_matches_boba(&encoded[pos..]);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_boba_on_malformed_input() {
+        let inputs: [&[u8]; 12] = [
+            b"",
+            b"x",
+            b"xx",
+            b"xexa",
+            b"exax",
+            b"abcde",
+            b"xbcdx",
+            b"x\xF0\x9F\xA6\x80x",
+            b"xigak-nyryk-humil-bosek-sonxx",
+            b"xigak-nyryk-humil-bosek-sonaxx",
+            b"xigak+nyryk-humil-bosek-sonax",
+            b"xesef-disof-gytuf-katof-movif-baxxx",
+        ];
+        for encoded in inputs {
+            assert_matches_boba(encoded);
+        }
+    }
+}
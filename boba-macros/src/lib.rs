@@ -0,0 +1,129 @@
+#![warn(clippy::all)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::cast_possible_truncation)]
+#![warn(clippy::cargo)]
+#![allow(unknown_lints)]
+#![warn(missing_copy_implementations)]
+#![warn(missing_docs)]
+#![warn(missing_debug_implementations)]
+#![warn(rust_2018_idioms)]
+#![warn(trivial_casts, trivial_numeric_casts)]
+#![warn(unused_qualifications)]
+#![warn(variant_size_differences)]
+#![forbid(unsafe_code)]
+
+//! Procedural macros for the [`boba`] crate.
+//!
+//! Use these macros through the **macros** feature of [`boba`], which
+//! re-exports them, for example as [`boba::bubblebabble!`].
+//!
+//! [`boba`]: https://docs.rs/boba
+//! [`boba::bubblebabble!`]: https://docs.rs/boba/latest/boba/macro.bubblebabble.html
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+mod decode;
+
+/// Decode a Bubble Babble string literal at compile time.
+///
+/// The literal is decoded with the same rules as [`boba::decode`], and the
+/// macro expands to a `&'static [u8; N]` byte string of the decoded bytes.
+/// Both string literals and byte string literals are accepted.
+///
+/// # Examples
+///
+/// ```
+/// const FINGERPRINT: &[u8; 9] = boba::bubblebabble!("xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(FINGERPRINT, b"Pineapple");
+///
+/// assert_eq!(boba::bubblebabble!(b"xexax"), b"");
+/// ```
+///
+/// # Errors
+///
+/// If the literal is not a valid Bubble Babble encoding, compilation fails
+/// with the `DecodeError` that [`boba::decode`] would return at runtime,
+/// reported at the literal.
+///
+/// ```compile_fail
+/// // error: invalid Bubble Babble literal: Expected vowel, got something else (DecodeError::ExpectedVowel)
+/// let fingerprint = boba::bubblebabble!("xigak-nyryk-humil-bosek-sonxx");
+/// ```
+///
+/// ```compile_fail
+/// // error: invalid Bubble Babble literal: Encountered byte outside of encoding alphabet at position 12 (DecodeError::InvalidByte(12))
+/// let fingerprint = boba::bubblebabble!("xigak-nyryk-Humil-bosek-sonax");
+/// ```
+///
+/// [`boba::decode`]: https://docs.rs/boba/latest/boba/fn.decode.html
+#[proc_macro]
+pub fn bubblebabble(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err((message, span)) => compile_error(&message, span),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, (String, Span)> {
+    let literal = literal(input)?;
+    let span = literal.span();
+    let encoded = match litrs::Literal::from(literal) {
+        litrs::Literal::String(lit) => lit.value().as_bytes().to_vec(),
+        litrs::Literal::ByteString(lit) => lit.value().to_vec(),
+        _ => return Err((String::from("expected a string literal"), span)),
+    };
+    match decode::decode(&encoded) {
+        Ok(decoded) => {
+            let mut decoded = Literal::byte_string(&decoded);
+            decoded.set_span(span);
+            Ok(TokenTree::Literal(decoded).into())
+        }
+        Err(err) => {
+            let message = format!(
+                "invalid Bubble Babble literal: {} (DecodeError::{:?})",
+                err, err
+            );
+            Err((message, span))
+        }
+    }
+}
+
+/// Extract the single literal token from the macro input.
+///
+/// Literals forwarded through `macro_rules!` fragments arrive wrapped in an
+/// invisible group, which is unwrapped.
+fn literal(input: TokenStream) -> Result<Literal, (String, Span)> {
+    let mut tokens = input.into_iter();
+    let token = match (tokens.next(), tokens.next()) {
+        (Some(token), None) => token,
+        (Some(_), Some(extra)) => {
+            return Err((
+                String::from("expected a single string literal"),
+                extra.span(),
+            ))
+        }
+        (None, _) => return Err((String::from("expected a string literal"), Span::call_site())),
+    };
+    match token {
+        TokenTree::Literal(literal) => Ok(literal),
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => literal(group.stream()),
+        token => Err((String::from("expected a string literal"), token.span())),
+    }
+}
+
+/// Expand to `compile_error!("message")` with every token at `span`.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    args.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(args),
+    ]
+    .into_iter()
+    .collect()
+}
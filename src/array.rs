@@ -0,0 +1,282 @@
+//! Encode and decode fixed-size arrays without allocating.
+
+use core::fmt;
+use core::ops::Deref;
+
+use crate::{decode, encode, encoded_len, DecodeError};
+
+/// A Bubble Babble encoding stored inline in an `M` byte array.
+///
+/// `M` is the length of the encoding, which is [`encoded_len`] of the input
+/// length. Stable Rust cannot compute an array length from a generic
+/// parameter, so `M` is usually spelled with a const block, like
+/// `EncodedArray<{ boba::encoded_len(32) }>` for the encoding of a 32 byte
+/// digest.
+///
+/// `EncodedArray` is [`Copy`], dereferences to [`str`], and implements
+/// [`Display`](fmt::Display).
+///
+/// # Examples
+///
+/// ```
+/// use boba::EncodedArray;
+///
+/// let encoded: EncodedArray<{ boba::encoded_len(9) }> = boba::encode_array(b"Pineapple");
+/// assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(encoded.len(), 29);
+/// assert_eq!(encoded.to_string(), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EncodedArray<const M: usize> {
+    bytes: [u8; M],
+}
+
+impl<const M: usize> EncodedArray<M> {
+    /// Wrap an array that holds a complete Bubble Babble encoding.
+    pub(crate) const fn from_bytes(bytes: [u8; M]) -> Self {
+        Self { bytes }
+    }
+
+    /// Return the encoding as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        encode::ascii(&self.bytes)
+    }
+
+    /// Return the encoding as a byte array.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; M] {
+        &self.bytes
+    }
+
+    /// Consume the encoding, returning the underlying byte array.
+    #[must_use]
+    pub const fn into_bytes(self) -> [u8; M] {
+        self.bytes
+    }
+}
+
+impl<const M: usize> Deref for EncodedArray<M> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const M: usize> AsRef<str> for EncodedArray<M> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const M: usize> AsRef<[u8]> for EncodedArray<M> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<const M: usize> fmt::Debug for EncodedArray<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const M: usize> fmt::Display for EncodedArray<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const M: usize> PartialEq<str> for EncodedArray<M> {
+    fn eq(&self, other: &str) -> bool {
+        self.bytes[..] == *other.as_bytes()
+    }
+}
+
+impl<const M: usize> PartialEq<&str> for EncodedArray<M> {
+    fn eq(&self, other: &&str) -> bool {
+        self.bytes[..] == *other.as_bytes()
+    }
+}
+
+/// Compile time check that `M` is the encoded length of `N` bytes.
+struct Lengths<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> Lengths<N, M> {
+    const VALID: () = assert!(
+        M == encoded_len(N),
+        "EncodedArray length must be boba::encoded_len of the input length"
+    );
+}
+
+/// Encode a fixed-size array with the Bubble Babble encoding to an
+/// [`EncodedArray`] without allocating.
+///
+/// The encoded length `M` is usually inferred from the annotated type of the
+/// result. Choosing an `M` other than [`encoded_len(N)`](encoded_len) is a
+/// compile error.
+///
+/// # Examples
+///
+/// ```
+/// use boba::EncodedArray;
+///
+/// let digest = [0xa5; 32];
+/// let encoded: EncodedArray<{ boba::encoded_len(32) }> = boba::encode_array(&digest);
+/// assert_eq!(boba::decode_array(encoded), Ok(digest));
+/// ```
+///
+/// ```compile_fail
+/// // The encoding of 32 bytes is 101 bytes long, not 100.
+/// let encoded: boba::EncodedArray<100> = boba::encode_array(&[0; 32]);
+/// ```
+#[must_use]
+pub fn encode_array<const N: usize, const M: usize>(data: &[u8; N]) -> EncodedArray<M> {
+    #[allow(clippy::let_unit_value)]
+    let () = Lengths::<N, M>::VALID;
+    let mut bytes = [0; M];
+    encode::encode_exact(data, &mut bytes);
+    EncodedArray::from_bytes(bytes)
+}
+
+/// Encode a fixed-size array with the Bubble Babble encoding in a const
+/// context.
+///
+/// This produces the same [`EncodedArray`] as [`encode_array`], but can be
+/// evaluated at compile time, for example to embed well-known fingerprints as
+/// constants. At runtime, prefer [`encode_array`], which is faster.
+///
+/// Choosing an `M` other than [`encoded_len(N)`](encoded_len) is a compile
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// use boba::EncodedArray;
+///
+/// const FINGERPRINT: [u8; 9] = *b"Pineapple";
+/// const ENCODED: EncodedArray<{ boba::encoded_len(9) }> = boba::const_encode(&FINGERPRINT);
+///
+/// assert_eq!(ENCODED, "xigak-nyryk-humil-bosek-sonax");
+/// ```
+///
+/// ```compile_fail
+/// const ENCODED: boba::EncodedArray<29> = boba::const_encode(b"1234567890");
+/// ```
+#[must_use]
+pub const fn const_encode<const N: usize, const M: usize>(data: &[u8; N]) -> EncodedArray<M> {
+    #[allow(clippy::let_unit_value)]
+    let () = Lengths::<N, M>::VALID;
+    EncodedArray::from_bytes(encode::encode_const(data))
+}
+
+/// Decode Bubble Babble-encoded byte slice to a fixed-size array without
+/// allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let decoded: [u8; 9] = boba::decode_array("xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(&decoded, b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and might return [`DecodeError`] under the same
+/// conditions as [`decode`](crate::decode()).
+///
+/// If `encoded` is a valid encoding of anything other than exactly `N` bytes,
+/// [`DecodeError::Corrupted`] is returned.
+///
+/// ```
+/// # use boba::DecodeError;
+/// let decoded = boba::decode_array::<_, 8>("xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(decoded, Err(DecodeError::Corrupted));
+/// ```
+pub fn decode_array<T: AsRef<[u8]>, const N: usize>(encoded: T) -> Result<[u8; N], DecodeError> {
+    let encoded = encoded.as_ref();
+    let mut decoded = [0; N];
+    if decode::inner_to_slice(encoded, &mut decoded)? == N {
+        Ok(decoded)
+    } else {
+        Err(DecodeError::Corrupted)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{const_encode, decode_array, encode_array, EncodedArray};
+    use crate::{encode, encoded_len, DecodeError};
+
+    #[test]
+    fn round_trip() {
+        let data = *b"1234567890";
+        let encoded: EncodedArray<{ encoded_len(10) }> = encode_array(&data);
+        assert_eq!(encoded, "xesef-disof-gytuf-katof-movif-baxux");
+        assert_eq!(encoded.as_str(), encode(data));
+        assert_eq!(decode_array(encoded), Ok(data));
+
+        let empty: EncodedArray<5> = encode_array(&[]);
+        assert_eq!(empty, "xexax");
+        assert_eq!(decode_array(empty), Ok([]));
+    }
+
+    #[test]
+    fn const_encode_matches_encode() {
+        const EMPTY: EncodedArray<5> = const_encode(&[]);
+        const ODD: EncodedArray<{ encoded_len(9) }> = const_encode(b"Pineapple");
+        const EVEN: EncodedArray<{ encoded_len(10) }> = const_encode(b"1234567890");
+        assert_eq!(EMPTY, "xexax");
+        assert_eq!(ODD, "xigak-nyryk-humil-bosek-sonax");
+        assert_eq!(EVEN, "xesef-disof-gytuf-katof-movif-baxux");
+
+        let bytes = (0..=255_u8).collect::<Vec<_>>();
+        for window in bytes.windows(7) {
+            let mut data = [0; 7];
+            data.copy_from_slice(window);
+            let encoded: EncodedArray<{ encoded_len(7) }> = const_encode(&data);
+            assert_eq!(encoded, encode_array(&data));
+        }
+        let mut data = [0; 32];
+        data.copy_from_slice(&bytes[224..]);
+        let encoded: EncodedArray<{ encoded_len(32) }> = const_encode(&data);
+        assert_eq!(encoded.as_str(), encode(data));
+    }
+
+    #[test]
+    fn copy_and_debug() {
+        let encoded: EncodedArray<{ encoded_len(9) }> = encode_array(b"Pineapple");
+        let copy = encoded;
+        assert_eq!(copy, encoded);
+        assert_eq!(
+            alloc::format!("{:?}", encoded),
+            "\"xigak-nyryk-humil-bosek-sonax\""
+        );
+    }
+
+    #[test]
+    fn decode_rejects_other_lengths() {
+        // 9 and 8 bytes have encodings of the same length.
+        assert_eq!(
+            decode_array::<_, 8>("xigak-nyryk-humil-bosek-sonax"),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(
+            decode_array::<_, 10>("xigak-nyryk-humil-bosek-sonax"),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(decode_array::<_, 32>("xexax"), Err(DecodeError::Corrupted));
+        assert_eq!(
+            decode_array::<_, 9>("xigak-nyryk-Humil-bosek-sonax"),
+            Err(DecodeError::InvalidByte(12))
+        );
+    }
+}
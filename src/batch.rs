@@ -0,0 +1,211 @@
+//! Encode many inputs into one contiguous buffer.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+use crate::encode;
+
+/// An encoder that appends the encodings of many inputs to a single buffer.
+///
+/// Encoding many short inputs, like 32 byte hashes, with [`encode`] allocates
+/// a [`String`] per input. `BatchEncoder` instead writes every encoding back
+/// to back into one [`String`] and records where each one starts in an
+/// offsets index. Calling [`clear`](Self::clear) keeps both allocations, so a
+/// single `BatchEncoder` can be reused for batch after batch without
+/// allocating.
+///
+/// The encoding at index `i` spans `offsets()[i]..offsets()[i + 1]` of the
+/// buffer returned by [`as_str`](Self::as_str).
+///
+/// [`encode`]: crate::encode()
+///
+/// # Examples
+///
+/// ```
+/// let mut batch = boba::BatchEncoder::new();
+/// batch.extend(["Pineapple", "1234567890"].iter());
+/// batch.push([]);
+///
+/// assert_eq!(batch.len(), 3);
+/// assert_eq!(batch.get(0), Some("xigak-nyryk-humil-bosek-sonax"));
+/// assert_eq!(batch.get(2), Some("xexax"));
+/// assert_eq!(batch.offsets(), [0, 29, 64, 69]);
+/// assert_eq!(
+///     batch.iter().collect::<Vec<_>>(),
+///     [
+///         "xigak-nyryk-humil-bosek-sonax",
+///         "xesef-disof-gytuf-katof-movif-baxux",
+///         "xexax",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BatchEncoder {
+    buf: String,
+    offsets: Vec<usize>,
+}
+
+impl Default for BatchEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchEncoder {
+    /// Construct a new, empty `BatchEncoder`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buf: String::new(),
+            offsets: vec![0],
+        }
+    }
+
+    /// Construct a new, empty `BatchEncoder` with room for `inputs` encodings
+    /// of inputs that are each `input_len` bytes long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut batch = boba::BatchEncoder::with_capacity(1000, 32);
+    /// for _ in 0..1000 {
+    ///     batch.push([0xa5; 32]);
+    /// }
+    /// assert_eq!(batch.as_str().len(), 1000 * boba::encoded_len(32));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the total encoded length overflows `usize`, this function panics.
+    #[must_use]
+    pub fn with_capacity(inputs: usize, input_len: usize) -> Self {
+        let len = encode::encoded_len(input_len)
+            .and_then(|len| len.checked_mul(inputs))
+            .expect("encoded length overflows usize");
+        let mut offsets = Vec::with_capacity(inputs + 1);
+        offsets.push(0);
+        Self {
+            buf: String::with_capacity(len),
+            offsets,
+        }
+    }
+
+    /// Encode `data` and append it to the batch, returning its index.
+    pub fn push<T: AsRef<[u8]>>(&mut self, data: T) -> usize {
+        encode::inner_into(data.as_ref(), &mut self.buf);
+        self.offsets.push(self.buf.len());
+        self.offsets.len() - 2
+    }
+
+    /// Return the encoding at `index`, or `None` if `index` is out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let start = *self.offsets.get(index)?;
+        let end = *self.offsets.get(index + 1)?;
+        self.buf.get(start..end)
+    }
+
+    /// Return an iterator over the encodings in the batch, in the order they
+    /// were pushed.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.offsets
+            .windows(2)
+            .map(move |window| &self.buf[window[0]..window[1]])
+    }
+
+    /// The number of encodings in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Whether the batch contains no encodings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The concatenated encodings, without delimiters.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// The offsets of the encodings in [`as_str`](Self::as_str).
+    ///
+    /// The offsets index always starts with `0` and has one more entry than
+    /// there are encodings in the batch.
+    #[must_use]
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Remove all encodings from the batch, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.offsets.truncate(1);
+    }
+
+    /// Consume the batch, returning the concatenated encodings and the offsets
+    /// index.
+    #[must_use]
+    pub fn into_parts(self) -> (String, Vec<usize>) {
+        (self.buf, self.offsets)
+    }
+}
+
+impl<T: AsRef<[u8]>> Extend<T> for BatchEncoder {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.offsets.reserve(iter.size_hint().0);
+        for data in iter {
+            self.push(data);
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for BatchEncoder {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        batch.extend(iter);
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::BatchEncoder;
+    use crate::encode;
+
+    #[test]
+    fn matches_encode() {
+        let inputs = (0..=255_u8)
+            .map(|len| (0..len).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let batch = inputs.iter().collect::<BatchEncoder>();
+        assert_eq!(batch.len(), inputs.len());
+        for (index, input) in inputs.iter().enumerate() {
+            assert_eq!(batch.get(index).unwrap(), encode(input));
+        }
+        assert_eq!(batch.get(inputs.len()), None);
+        let concatenated = inputs.iter().map(encode).collect::<Vec<_>>().concat();
+        assert_eq!(batch.as_str(), concatenated);
+    }
+
+    #[test]
+    fn clear_keeps_capacity() {
+        let mut batch = BatchEncoder::with_capacity(16, 32);
+        let capacity = batch.as_str().len() + batch.buf.capacity();
+        batch.extend((0..16).map(|byte| [byte; 32]));
+        batch.clear();
+        assert!(batch.is_empty());
+        assert_eq!(batch.offsets(), [0]);
+        assert_eq!(batch.buf.capacity(), capacity);
+        assert_eq!(batch.push("Pineapple"), 0);
+        assert_eq!(batch.get(0), Some("xigak-nyryk-humil-bosek-sonax"));
+    }
+}
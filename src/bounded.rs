@@ -0,0 +1,165 @@
+//! Encode and decode into fixed-capacity containers without allocating.
+
+#[cfg(feature = "arrayvec")]
+use arrayvec::{ArrayString, ArrayVec};
+
+use crate::{decode, encode, DecodeError, EncodeError};
+
+#[cfg(feature = "heapless")]
+pub fn encode_heapless<const N: usize>(data: &[u8]) -> Result<heapless::String<N>, EncodeError> {
+    let len = encode::encoded_len(data.len())
+        .filter(|&len| len <= N)
+        .ok_or(EncodeError::BufferTooSmall)?;
+    let mut encoded = heapless::Vec::<u8, N>::new();
+    encoded
+        .resize_default(len)
+        .expect("encoded length is checked against capacity");
+    encode::encode_exact(data, &mut encoded);
+    let encoded = heapless::String::from_utf8(encoded).expect("Bubble Babble encodings are ASCII");
+    Ok(encoded)
+}
+
+#[cfg(feature = "heapless")]
+pub fn decode_heapless<const N: usize>(
+    encoded: &[u8],
+) -> Result<heapless::Vec<u8, N>, DecodeError> {
+    let mut decoded = heapless::Vec::<u8, N>::new();
+    decoded
+        .resize_default(N)
+        .expect("vec is resized to exactly its capacity");
+    let len = decode::inner_to_slice(encoded, &mut decoded)?;
+    decoded.truncate(len);
+    Ok(decoded)
+}
+
+#[cfg(feature = "arrayvec")]
+pub fn encode_arrayvec<const N: usize>(data: &[u8]) -> Result<ArrayString<N>, EncodeError> {
+    let mut buf = [0; N];
+    let len = encode::inner_to_slice(data, &mut buf).ok_or(EncodeError::BufferTooSmall)?;
+    let mut encoded = ArrayString::new();
+    encoded.push_str(encode::ascii(&buf[..len]));
+    Ok(encoded)
+}
+
+#[cfg(feature = "arrayvec")]
+pub fn decode_arrayvec<const N: usize>(encoded: &[u8]) -> Result<ArrayVec<u8, N>, DecodeError> {
+    let mut decoded = ArrayVec::from([0; N]);
+    let len = decode::inner_to_slice(encoded, &mut decoded)?;
+    decoded.truncate(len);
+    Ok(decoded)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{encode, DecodeError, EncodeError};
+
+    const INPUTS: [&[u8]; 4] = [b"", b"a", b"Pineapple", b"1234567890"];
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn heapless_round_trip() {
+        for &data in &INPUTS {
+            let encoded = super::encode_heapless::<35>(data).unwrap();
+            assert_eq!(encoded, encode(data).as_str());
+            let decoded = super::decode_heapless::<10>(encoded.as_bytes()).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn heapless_capacity() {
+        assert_eq!(
+            super::encode_heapless::<28>(b"Pineapple"),
+            Err(EncodeError::BufferTooSmall)
+        );
+        let encoded = super::encode_heapless::<29>(b"Pineapple").unwrap();
+        assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+        assert_eq!(
+            super::decode_heapless::<8>(encoded.as_bytes()),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(
+            super::decode_heapless::<9>(b"xigak-nyryk-Humil-bosek-sonax"),
+            Err(DecodeError::InvalidByte(12))
+        );
+    }
+
+    /// Valid input that does not fit in the output reports `Corrupted`, and
+    /// invalid input reports its own error whatever the capacity.
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn heapless_capacity_error_is_corrupted() {
+        for (encoded, err) in [
+            ("xigak-nyryk-humil-bosek-sonax", DecodeError::Corrupted),
+            (
+                "xesef-disof-gytuf-katof-movif-baxax",
+                DecodeError::ChecksumMismatch,
+            ),
+            (
+                "xigak-nyryk-humil-bosek-sonax-",
+                DecodeError::MalformedTrailer,
+            ),
+            (
+                "xigak-nyryk-Humil-bosek-sonax",
+                DecodeError::InvalidByte(12),
+            ),
+        ] {
+            assert_eq!(super::decode_heapless::<1>(encoded.as_bytes()), Err(err));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn arrayvec_round_trip() {
+        for &data in &INPUTS {
+            let encoded = super::encode_arrayvec::<35>(data).unwrap();
+            assert_eq!(encoded.as_str(), encode(data));
+            let decoded = super::decode_arrayvec::<10>(encoded.as_bytes()).unwrap();
+            assert_eq!(decoded.as_slice(), data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn arrayvec_capacity() {
+        assert_eq!(
+            super::encode_arrayvec::<28>(b"Pineapple"),
+            Err(EncodeError::BufferTooSmall)
+        );
+        let encoded = super::encode_arrayvec::<29>(b"Pineapple").unwrap();
+        assert_eq!(encoded.as_str(), "xigak-nyryk-humil-bosek-sonax");
+        assert_eq!(
+            super::decode_arrayvec::<8>(encoded.as_bytes()),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(
+            super::decode_arrayvec::<9>(b"xigak-nyryk-Humil-bosek-sonax"),
+            Err(DecodeError::InvalidByte(12))
+        );
+    }
+
+    /// Valid input that does not fit in the output reports `Corrupted`, and
+    /// invalid input reports its own error whatever the capacity.
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn arrayvec_capacity_error_is_corrupted() {
+        for (encoded, err) in [
+            ("xigak-nyryk-humil-bosek-sonax", DecodeError::Corrupted),
+            (
+                "xesef-disof-gytuf-katof-movif-baxax",
+                DecodeError::ChecksumMismatch,
+            ),
+            (
+                "xigak-nyryk-humil-bosek-sonax-",
+                DecodeError::MalformedTrailer,
+            ),
+            (
+                "xigak-nyryk-Humil-bosek-sonax",
+                DecodeError::InvalidByte(12),
+            ),
+        ] {
+            assert_eq!(super::decode_arrayvec::<1>(encoded.as_bytes()), Err(err));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Encode from [`Buf`] and decode into [`BufMut`] without flattening.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "alloc")]
+use bytes::Buf;
+use bytes::BufMut;
+
+use crate::decode::{self, Sink};
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use crate::Encoder;
+
+#[cfg(feature = "alloc")]
+pub fn encode<B: Buf>(mut data: B) -> String {
+    let len = crate::encode::encoded_len(data.remaining()).expect("encoded length overflows usize");
+    let mut encoded = String::with_capacity(len);
+    let mut stream = Encoder::new();
+    while data.has_remaining() {
+        let chunk = data.chunk();
+        let consumed = chunk.len();
+        stream.update(&mut encoded, chunk);
+        data.advance(consumed);
+    }
+    stream.finish(&mut encoded);
+    encoded
+}
+
+/// The number of decoded bytes buffered on the stack before being written to
+/// the [`BufMut`].
+const BLOCK_LEN: usize = 512;
+
+/// Buffers decoded bytes in a stack block, writing each full block to `out`.
+///
+/// Without an `out`, full blocks are discarded and only counted, which
+/// validates the input without writing anything.
+struct BlockSink<'a, B> {
+    out: Option<&'a mut B>,
+    block: [u8; BLOCK_LEN],
+    len: usize,
+    total: usize,
+}
+
+impl<'a, B: BufMut> BlockSink<'a, B> {
+    fn new(out: Option<&'a mut B>) -> Self {
+        Self {
+            out,
+            block: [0; BLOCK_LEN],
+            len: 0,
+            total: 0,
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(out) = self.out.as_mut() {
+            out.put_slice(&self.block[..self.len]);
+        }
+        self.len = 0;
+    }
+}
+
+impl<B: BufMut> Sink for BlockSink<'_, B> {
+    #[inline]
+    fn push_byte(&mut self, byte: u8) {
+        if self.len == BLOCK_LEN {
+            self.flush();
+        }
+        self.block[self.len] = byte;
+        self.len += 1;
+        self.total += 1;
+    }
+}
+
+pub fn decode_into<B: BufMut>(decoded: &mut B, encoded: &[u8]) -> Result<(), DecodeError> {
+    // Validate the whole input before writing anything so `decoded` is left
+    // untouched on error. The validation pass also counts the decoded bytes,
+    // so the capacity of `decoded` is checked against the exact length.
+    // Output that fits in a single block is written straight from the
+    // validation pass.
+    let mut validated = BlockSink::<B>::new(None);
+    decode::inner_with(encoded, &mut validated)?;
+    if decoded.remaining_mut() < validated.total {
+        return Err(DecodeError::Corrupted);
+    }
+    if validated.total <= BLOCK_LEN {
+        decoded.put_slice(&validated.block[..validated.len]);
+        return Ok(());
+    }
+    let mut sink = BlockSink::new(Some(decoded));
+    decode::inner_with(encoded, &mut sink)?;
+    sink.flush();
+    Ok(())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use bytes::{Buf, Bytes, BytesMut};
+
+    use super::{decode_into, encode};
+    use crate::DecodeError;
+
+    #[test]
+    fn encode_split_chunks() {
+        let data = (0..=255_u8).collect::<Vec<_>>();
+        for len in [0, 1, 2, 9, 10, 255, 256] {
+            let data = &data[..len];
+            let expected = crate::encode(data);
+            assert_eq!(encode(data), expected);
+            for split in 0..=len {
+                let (front, back) = data.split_at(split);
+                let chain = Bytes::copy_from_slice(front).chain(Bytes::copy_from_slice(back));
+                assert_eq!(encode(chain), expected, "len {} split at {}", len, split);
+            }
+        }
+    }
+
+    /// A `Buf` that yields one byte per chunk.
+    struct Bytewise<'a>(&'a [u8]);
+
+    impl Buf for Bytewise<'_> {
+        fn remaining(&self) -> usize {
+            self.0.len()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            &self.0[..self.0.len().min(1)]
+        }
+
+        fn advance(&mut self, cnt: usize) {
+            self.0 = &self.0[cnt..];
+        }
+    }
+
+    #[test]
+    fn encode_single_byte_chunks() {
+        assert_eq!(encode(Bytewise(b"")), "xexax");
+        assert_eq!(
+            encode(Bytewise(b"Pineapple")),
+            "xigak-nyryk-humil-bosek-sonax"
+        );
+        assert_eq!(
+            encode(Bytewise(b"1234567890")),
+            "xesef-disof-gytuf-katof-movif-baxux"
+        );
+        let nested = (&b"12"[..])
+            .chain(&b"3"[..])
+            .chain((&b"456"[..]).chain(&b""[..]).chain(&b"7890"[..]));
+        assert_eq!(encode(nested), "xesef-disof-gytuf-katof-movif-baxux");
+    }
+
+    #[test]
+    fn decode_into_buf_mut() {
+        let mut decoded = BytesMut::from(&b"fingerprint: "[..]);
+        decode_into(&mut decoded, b"xigak-nyryk-humil-bosek-sonax").unwrap();
+        assert_eq!(decoded, &b"fingerprint: Pineapple"[..]);
+
+        let mut buf = [0; 16];
+        let mut out = &mut buf[..];
+        decode_into(&mut out, b"xesef-disof-gytuf-katof-movif-baxux").unwrap();
+        assert_eq!(out.len(), 6);
+        assert_eq!(&buf[..10], b"1234567890");
+
+        let mut decoded = Vec::new();
+        assert_eq!(
+            decode_into(&mut decoded, b"xigak-nyryk-Humil-bosek-sonax"),
+            Err(DecodeError::InvalidByte(12))
+        );
+    }
+
+    #[test]
+    fn decode_into_buf_mut_spanning_blocks() {
+        for len in [511, 512, 513, 1024, 1025, 4000] {
+            let data = (0..len).map(|i: usize| (i % 251) as u8).collect::<Vec<_>>();
+            let mut decoded = BytesMut::new();
+            decode_into(&mut decoded, crate::encode(&data).as_bytes()).unwrap();
+            assert_eq!(decoded, data[..], "len {}", len);
+        }
+    }
+
+    #[test]
+    fn decode_into_fixed_capacity_buf_mut_does_not_panic() {
+        let mut buf = [0xFF; 8];
+        let mut out = &mut buf[..];
+        assert_eq!(
+            decode_into(&mut out, b"xigak-nyryk-humil-bosek-sonax"),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(out.len(), 8);
+        assert_eq!(buf, [0xFF; 8]);
+    }
+
+    #[test]
+    fn decode_into_exact_capacity_buf_mut() {
+        let mut buf = [0; 9];
+        let mut out = &mut buf[..];
+        decode_into(&mut out, b"xigak-nyryk-humil-bosek-sonax").unwrap();
+        assert!(out.is_empty());
+        assert_eq!(&buf, b"Pineapple");
+    }
+
+    #[test]
+    fn decode_into_small_buf_mut_reports_decode_errors() {
+        let mut buf = [0; 1];
+        let mut out = &mut buf[..];
+        assert_eq!(
+            decode_into(&mut out, b"xigak-nyryk-Humil-bosek-sonax"),
+            Err(DecodeError::InvalidByte(12))
+        );
+        assert_eq!(
+            decode_into(&mut out, b"xigak-nyryk-humil-bosek-sonax-"),
+            Err(DecodeError::MalformedTrailer)
+        );
+        assert_eq!(out.len(), 1);
+    }
+
+    #[test]
+    fn decode_into_buf_mut_is_untouched_on_error() {
+        let data = (0..2000)
+            .map(|i: usize| (i % 251) as u8)
+            .collect::<Vec<_>>();
+        let mut encoded = crate::encode(&data).into_bytes();
+        // Corrupt the final tuple so the error is found after several blocks
+        // have been decoded.
+        let len = encoded.len();
+        encoded[len - 4] = b'b';
+        let mut decoded = BytesMut::from(&b"prefix"[..]);
+        assert_eq!(
+            decode_into(&mut decoded, &encoded),
+            Err(DecodeError::ExpectedVowel)
+        );
+        assert_eq!(decoded, &b"prefix"[..]);
+
+        let mut decoded = BytesMut::new();
+        assert_eq!(
+            decode_into(&mut decoded, b"xigak-nyryk-humil-bosek-sonxx"),
+            Err(DecodeError::ExpectedVowel)
+        );
+        assert!(decoded.is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+//! Parse Bubble Babble command line arguments with [`clap`].
+//!
+//! [`BubbleBabbleParser`] is a [`TypedValueParser`] that decodes argument
+//! values with [`boba::decode`](crate::decode()). Decoding failures are
+//! reported as clap validation errors. When the input contains a byte outside
+//! of the encoding alphabet, the error points at the offending character.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap::{Arg, Command};
+//!
+//! let cmd = Command::new("verify").arg(
+//!     Arg::new("fingerprint").value_parser(boba::clap::BubbleBabbleParser::new()),
+//! );
+//!
+//! let matches = cmd
+//!     .clone()
+//!     .try_get_matches_from(["verify", "xigak-nyryk-humil-bosek-sonax"])
+//!     .unwrap();
+//! let fingerprint = matches.get_one::<Vec<u8>>("fingerprint").unwrap();
+//! assert_eq!(fingerprint, b"Pineapple");
+//!
+//! let err = cmd
+//!     .try_get_matches_from(["verify", "xigak-nyryk-Humil-bosek-sonax"])
+//!     .unwrap_err();
+//! assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+//! ```
+//!
+//! [`clap`]: https://docs.rs/clap
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+use std::ffi::OsStr;
+
+use ::clap::builder::TypedValueParser;
+use ::clap::error::{Error, ErrorKind};
+use ::clap::{Arg, Command};
+
+use crate::DecodeError;
+
+/// A clap value parser that decodes Bubble Babble arguments to
+/// [`Vec<u8>`](Vec).
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BubbleBabbleParser {
+    _private: (),
+}
+
+impl BubbleBabbleParser {
+    /// Construct a new `BubbleBabbleParser`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TypedValueParser for BubbleBabbleParser {
+    type Value = Vec<u8>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        // `OsStr::as_encoded_bytes` is stable since Rust 1.74.0. This is newer
+        // than the MSRV of `boba`, but this module requires `clap` 4.5, which
+        // has an MSRV of 1.74.0.
+        #[allow(clippy::incompatible_msrv)]
+        let encoded = value.as_encoded_bytes();
+        crate::decode(encoded).map_err(|err| {
+            let message = validation_message(arg, encoded, err);
+            Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        })
+    }
+}
+
+/// Render a decoding failure, pointing at the invalid byte if there is one.
+fn validation_message(arg: Option<&Arg>, encoded: &[u8], err: DecodeError) -> String {
+    let value = String::from_utf8_lossy(encoded);
+    let mut message = String::new();
+    let _ = match arg {
+        Some(arg) => write!(message, "invalid value '{}' for '{}': {}", value, arg, err),
+        None => write!(message, "invalid value '{}': {}", value, err),
+    };
+    if let DecodeError::InvalidByte(pos) = err {
+        // Count the characters before the invalid byte so the caret lines up
+        // even when the input contains multi-byte characters.
+        let column = String::from_utf8_lossy(&encoded[..pos]).chars().count();
+        let _ = write!(
+            message,
+            "\n\n  {}\n  {:>width$}",
+            value,
+            "^",
+            width = column + 1
+        );
+    }
+    message.push('\n');
+    message
+}
+
+#[cfg(test)]
+#[allow(clippy::non_ascii_literal)]
+mod tests {
+    use ::clap::Arg;
+
+    use super::validation_message;
+    use crate::DecodeError;
+
+    #[test]
+    fn points_at_invalid_byte() {
+        let arg = Arg::new("fingerprint").required(true);
+        let message = validation_message(
+            Some(&arg),
+            b"xigak-nyryk-Humil-bosek-sonax",
+            DecodeError::InvalidByte(12),
+        );
+        assert_eq!(
+            message,
+            "invalid value 'xigak-nyryk-Humil-bosek-sonax' for '<fingerprint>': \
+             Encountered byte outside of encoding alphabet at position 12\n\
+             \n  xigak-nyryk-Humil-bosek-sonax\
+             \n              ^\n"
+        );
+    }
+
+    #[test]
+    fn points_at_multibyte_character() {
+        let message = validation_message(None, "xa💎🦀x".as_bytes(), DecodeError::InvalidByte(2));
+        assert!(message.ends_with("\n  xa💎🦀x\n    ^\n"));
+    }
+
+    #[test]
+    fn no_pointer_without_position() {
+        let message = validation_message(None, b"xexex", DecodeError::ChecksumMismatch);
+        assert_eq!(message, "invalid value 'xexex': Checksum mismatch\n");
+    }
+}
@@ -0,0 +1,190 @@
+//! Constant-time comparison of Bubble Babble encodings.
+//!
+//! Comparing user-supplied codes against stored codes with `==` returns as
+//! soon as a byte differs, which leaks how long the matching prefix is.
+//! Normalizing the user input with [`str::to_lowercase`] or by stripping
+//! whitespace branches on the input as well.
+//!
+//! The comparisons in this module normalize and compare their inputs with the
+//! primitives in [`subtle`]. Normalization:
+//!
+//! - Folds ASCII uppercase letters to lowercase.
+//! - Removes ASCII whitespace and `-` separators.
+//!
+//! The lengths of the inputs are not considered secret. Normalization does
+//! work quadratic in the input length, which is negligible for the short
+//! codes these functions are meant for. To bound that work when one input is
+//! untrusted, inputs that are much longer than the other input compare
+//! unequal without being normalized; see [`ct_eq_encoded`].
+//!
+//! Encodings are handled as plain strings and byte slices throughout `boba`,
+//! so these comparisons are free functions rather than methods on an encoding
+//! type. [`ct_eq_encoded`] compares two encodings, and [`ct_eq_bytes`]
+//! compares an encoding against raw bytes. The
+//! [`boba::serde::BubbleBabble`] adapter holds no encoding and has no
+//! comparison methods.
+//!
+//! [`boba::serde::BubbleBabble`]: https://docs.rs/boba/latest/boba/serde/struct.BubbleBabble.html
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater};
+
+/// Compare two Bubble Babble encodings for equality in constant time.
+///
+/// The encodings are normalized before comparing: ASCII letters are compared
+/// case-insensitively, and ASCII whitespace and `-` separators are ignored.
+/// Neither input is decoded, so malformed encodings compare equal if they
+/// normalize to the same bytes.
+///
+/// An input more than twice as long as the other input, plus a small
+/// allowance for surrounding whitespace, compares unequal without being
+/// normalized. This bounds the work done on an untrusted input by the length
+/// of the trusted one. Well-formed encodings, with or without separators,
+/// never exceed the bound.
+///
+/// # Examples
+///
+/// ```
+/// let stored = "xigak-nyryk-humil-bosek-sonax";
+/// assert!(bool::from(boba::ct_eq_encoded(stored, "XIGAK NYRYK HUMIL BOSEK SONAX")));
+/// assert!(bool::from(boba::ct_eq_encoded(stored, " xigak-nyryk-humil-bosek-sonax\n")));
+/// assert!(!bool::from(boba::ct_eq_encoded(stored, "xigak-nyryk-humil-bosek-sonox")));
+/// ```
+#[must_use]
+pub fn ct_eq_encoded<T, U>(a: T, b: U) -> Choice
+where
+    T: AsRef<[u8]>,
+    U: AsRef<[u8]>,
+{
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let len = a.len().max(b.len());
+    if len > max_len(a.len().min(b.len())) {
+        return Choice::from(0);
+    }
+    let (a, a_len) = normalize(a, len);
+    let (b, b_len) = normalize(b, len);
+    a_len.ct_eq(&b_len) & a.ct_eq(&b)
+}
+
+/// Compare a Bubble Babble encoding against the encoding of raw bytes in
+/// constant time.
+///
+/// `data` is encoded with the constant-time encoder in [`ct`](crate::ct) and
+/// compared to `encoded` with [`ct_eq_encoded`].
+///
+/// # Examples
+///
+/// ```
+/// assert!(bool::from(boba::ct_eq_bytes("XIGAK NYRYK HUMIL BOSEK SONAX", "Pineapple")));
+/// assert!(!bool::from(boba::ct_eq_bytes("xigak-nyryk-humil-bosek-sonax", "Pineapples")));
+/// ```
+#[must_use]
+pub fn ct_eq_bytes<T, U>(encoded: T, data: U) -> Choice
+where
+    T: AsRef<[u8]>,
+    U: AsRef<[u8]>,
+{
+    ct_eq_encoded(encoded, crate::ct::encode(data))
+}
+
+/// The longest input that can be compared against an input of `len` bytes.
+///
+/// Every encoded byte is followed by at most one separator, plus some leading
+/// and trailing whitespace.
+const fn max_len(len: usize) -> usize {
+    const WHITESPACE_ALLOWANCE: usize = 16;
+    len.saturating_mul(2).saturating_add(WHITESPACE_ALLOWANCE)
+}
+
+/// Normalize `input` into a zero-padded buffer of `len` bytes, returning the
+/// buffer and the number of bytes written to it.
+fn normalize(input: &[u8], len: usize) -> (Vec<u8>, usize) {
+    let mut normalized = vec![0; len];
+    let mut written = 0_usize;
+    for &byte in input {
+        let is_upper = byte.ct_gt(&(b'A' - 1)) & (b'Z' + 1).ct_gt(&byte);
+        let byte = u8::conditional_select(&byte, &(byte | 0x20), is_upper);
+        let skip = byte.ct_eq(&b' ')
+            | byte.ct_eq(&b'\t')
+            | byte.ct_eq(&b'\n')
+            | byte.ct_eq(&b'\r')
+            | byte.ct_eq(&b'-');
+        let keep = !skip;
+        // Write to every slot so the memory access pattern does not depend on
+        // how many bytes have been skipped.
+        for (idx, slot) in normalized.iter_mut().enumerate() {
+            slot.conditional_assign(&byte, keep & idx.ct_eq(&written));
+        }
+        written += usize::from(keep.unwrap_u8());
+    }
+    (normalized, written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ct_eq_bytes, ct_eq_encoded, normalize};
+
+    #[test]
+    fn normalization() {
+        let (normalized, len) = normalize(b" XiGaK-\tNYRYK\r\n", 16);
+        assert_eq!(&normalized[..len], b"xigaknyryk");
+        assert!(normalized[len..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn different_lengths() {
+        assert!(!bool::from(ct_eq_encoded("xexax", "xexaxx")));
+        assert!(!bool::from(ct_eq_encoded("", "xexax")));
+        assert!(bool::from(ct_eq_encoded("", " - ")));
+    }
+
+    #[test]
+    fn long_input_is_not_normalized() {
+        let stored = "xigak-nyryk-humil-bosek-sonax";
+        let spaced = " x i g a k n y r y k h u m i l b o s e k s o n a x\n";
+        assert!(bool::from(ct_eq_encoded(stored, spaced)));
+        assert!(bool::from(ct_eq_encoded(spaced, stored)));
+
+        // Normalizing a megabyte of input would take quadratic time.
+        let mut long = alloc::string::String::from(stored);
+        long.push_str(&" ".repeat(1 << 20));
+        assert!(!bool::from(ct_eq_encoded(stored, &long)));
+        assert!(!bool::from(ct_eq_encoded(&long, stored)));
+        assert!(!bool::from(ct_eq_encoded("", &long)));
+    }
+
+    #[test]
+    fn encoded_and_raw_bytes_agree() {
+        let stored = "xigak-nyryk-humil-bosek-sonax";
+        for (input, equal) in [
+            ("xigak-nyryk-humil-bosek-sonax", true),
+            ("XIGAK NYRYK HUMIL BOSEK SONAX\n", true),
+            ("xigaknyrykhumilboseksonax", true),
+            ("xigak-nyryk-humil-bosek-sonox", false),
+            ("xigak-nyryk-humil-bosek", false),
+        ] {
+            assert_eq!(bool::from(ct_eq_encoded(stored, input)), equal, "{}", input);
+            assert_eq!(
+                bool::from(ct_eq_bytes(input, "Pineapple")),
+                equal,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn raw_bytes() {
+        assert!(bool::from(ct_eq_bytes("xexax", [])));
+        assert!(bool::from(ct_eq_bytes(
+            "xesefdisofgytufkatofmovifbaxux",
+            "1234567890"
+        )));
+        assert!(!bool::from(ct_eq_bytes(
+            "xesef-disof-gytuf-katof-movif-baxux",
+            "1234567891"
+        )));
+    }
+}
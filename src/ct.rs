@@ -0,0 +1,389 @@
+//! Constant-time Bubble Babble encoding and decoding.
+//!
+//! [`boba::encode`](crate::encode()) and [`boba::decode`](crate::decode())
+//! index lookup tables with the bytes being encoded, branch on the characters
+//! being decoded, and return early on the first invalid character. These
+//! timing side channels leak information about the data, which matters when
+//! encoding secret material like private recovery keys.
+//!
+//! The functions in this module produce the same results without
+//! data-dependent branches or table lookups:
+//!
+//! - Indices are mapped to and from vowels and consonants with branch-free
+//!   arithmetic.
+//! - The checksum is reduced with multiplication and shifts instead of
+//!   division.
+//! - Decoding inspects every character of the input before reporting an error.
+//!
+//! The length of the input and the length of the output are not considered
+//! secret.
+//!
+//! # Examples
+//!
+//! ```
+//! # use boba::DecodeError;
+//! # fn example() -> Result<(), DecodeError> {
+//! let encoded = boba::ct::encode("Pineapple");
+//! assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+//! assert_eq!(boba::ct::decode(encoded)?, b"Pineapple");
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::encode::{CONSONANTS, VOWELS};
+use crate::DecodeError;
+
+const HEADER: u8 = b'x';
+const TRAILER: u8 = b'x';
+const SEPARATOR: u8 = b'-';
+const MID: u8 = b'x';
+
+/// Encode a byte slice with the Bubble Babble encoding to a [`String`] in
+/// constant time.
+///
+/// The output is identical to [`boba::encode`](crate::encode()).
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(boba::ct::encode([]), "xexax");
+/// assert_eq!(boba::ct::encode("1234567890"), "xesef-disof-gytuf-katof-movif-baxux");
+/// ```
+#[must_use]
+pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
+    let data = data.as_ref();
+    let mut encoded = String::with_capacity(6 * (data.len() / 2) + 3 + 2);
+    encoded.push(HEADER.into());
+    let mut checksum = 1_u16;
+    let mut chunks = data.chunks_exact(2);
+    while let Some(&[left, right]) = chunks.next() {
+        odd_partial(left, checksum, &mut encoded);
+        let right = u16::from(right);
+        encoded.push(consonant(right >> 4));
+        encoded.push(SEPARATOR.into());
+        encoded.push(consonant(right & 15));
+        checksum = mod36(checksum * 5 + u16::from(left) * 7 + right);
+    }
+    if let [byte] = chunks.remainder() {
+        odd_partial(*byte, checksum, &mut encoded);
+    } else {
+        encoded.push(vowel(mod6(checksum)));
+        encoded.push(MID.into());
+        encoded.push(vowel(div6(checksum)));
+    }
+    encoded.push(TRAILER.into());
+    encoded
+}
+
+/// Decode a Bubble Babble-encoded byte slice to a [`Vec<u8>`](Vec) in
+/// constant time.
+///
+/// Valid inputs decode to the same bytes as [`boba::decode`](crate::decode()).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// assert_eq!(boba::ct::decode("xexax")?, vec![]);
+/// assert_eq!(boba::ct::decode("xesef-disof-gytuf-katof-movif-baxux")?, b"1234567890");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Reporting which character failed to decode would leak information about
+/// the input, so every decoding failure is reported as
+/// [`DecodeError::Corrupted`].
+///
+/// ```
+/// # use boba::DecodeError;
+/// assert_eq!(boba::ct::decode("x💎🦀x"), Err(DecodeError::Corrupted));
+/// assert_eq!(boba::ct::decode("yx"), Err(DecodeError::Corrupted));
+/// assert_eq!(
+///     boba::ct::decode("xesefxdisofxgytufxkatofxmovifxbaxux"),
+///     Err(DecodeError::Corrupted)
+/// );
+/// ```
+pub fn decode<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, DecodeError> {
+    let encoded = encoded.as_ref();
+    // Well-formed encodings are a header, any number of 6 character tuples, a
+    // 3 character partial tuple, and a trailer. The input length is public.
+    let len = encoded.len();
+    if len < 5 || (len - 5) % 6 != 0 {
+        return Err(DecodeError::Corrupted);
+    }
+    let (header, enc, trailer) = (encoded[0], &encoded[1..len - 1], encoded[len - 1]);
+    // `invalid` is `0xFF` if any error has been encountered and `0` otherwise.
+    let mut invalid = !eq(header, HEADER) | !eq(trailer, TRAILER);
+
+    let mut decoded = Vec::with_capacity(2 * ((len + 1) / 6));
+    let mut checksum = 1_u16;
+    let mut chunks = enc.chunks_exact(6);
+    while let Some(&[left, mid, right, up, separator, down]) = chunks.next() {
+        let (left, left_invalid) = index_from_vowel(left);
+        let (mid, mid_invalid) = index_from_consonant(mid);
+        let (right, right_invalid) = index_from_vowel(right);
+        let (up, up_invalid) = index_from_consonant(up);
+        let (down, down_invalid) = index_from_consonant(down);
+        let (byte1, byte1_invalid) = decode_3_tuple(left, mid, right, checksum);
+        let byte2 = (up << 4) | down;
+        invalid |= left_invalid | mid_invalid | right_invalid | up_invalid | down_invalid;
+        invalid |= byte1_invalid | !eq(separator, SEPARATOR);
+        checksum = mod36(checksum * 5 + u16::from(byte1) * 7 + u16::from(byte2));
+        decoded.push(byte1);
+        decoded.push(byte2);
+    }
+    if let [left, mid, right] = *chunks.remainder() {
+        let (a, a_invalid) = index_from_vowel(left);
+        let (c, c_invalid) = index_from_vowel(right);
+        invalid |= a_invalid | c_invalid;
+        // Whether the final partial tuple holds a byte determines the length
+        // of the output, which is public.
+        if mid == MID {
+            invalid |= !eq(a, mod6(checksum) as u8) | !eq(c, div6(checksum) as u8);
+        } else {
+            let (b, b_invalid) = index_from_consonant(mid);
+            let (byte, byte_invalid) = decode_3_tuple(a, b, c, checksum);
+            invalid |= b_invalid | byte_invalid;
+            decoded.push(byte);
+        }
+    }
+    if invalid == 0 {
+        Ok(decoded)
+    } else {
+        Err(DecodeError::Corrupted)
+    }
+}
+
+#[inline]
+fn odd_partial(raw_byte: u8, checksum: u16, buf: &mut String) {
+    let raw_byte = u16::from(raw_byte);
+    buf.push(vowel(mod6((raw_byte >> 6) + checksum)));
+    buf.push(consonant((raw_byte >> 2) & 15));
+    buf.push(vowel(mod6((raw_byte & 3) + div6(checksum))));
+}
+
+/// Decode the 3 character half of a tuple, returning the decoded byte and a
+/// mask that is `0xFF` if the tuple does not checksum properly.
+#[inline]
+fn decode_3_tuple(a: u8, b: u8, c: u8, checksum: u16) -> (u8, u8) {
+    let high = mod6(u16::from(a) + 6 - mod6(checksum));
+    let low = mod6(u16::from(c) + 6 - div6(checksum));
+    let invalid = (ge(high, 4) | ge(low, 4)) as u8;
+    let byte = ((high & 3) << 6) | (u16::from(b) << 2) | (low & 3);
+    (byte as u8, invalid.wrapping_neg())
+}
+
+/// Map an index in `0..6` to a vowel without indexing into `VOWELS`.
+#[inline]
+fn vowel(index: u16) -> char {
+    // a e i o u y
+    // 0 1 2 3 4 5
+    //
+    // Vowels are 4 apart, except for the gaps of 6 around `o`.
+    let byte = u16::from(b'a') + 4 * index + 2 * ge(index, 3) + 2 * ge(index, 4);
+    char::from(byte as u8)
+}
+
+/// Map an index in `0..16` to a consonant without indexing into `CONSONANTS`.
+#[inline]
+fn consonant(index: u16) -> char {
+    // b c d f g h k l m n p  r  s  t  v  z
+    // 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+    //
+    // Consonants are consecutive letters, skipping over `e`, `i` and `j`,
+    // `o`, `q`, `u`, and `w`, `x` and `y`.
+    let byte = u16::from(b'b')
+        + index
+        + ge(index, 3)
+        + 2 * ge(index, 6)
+        + ge(index, 10)
+        + ge(index, 11)
+        + ge(index, 14)
+        + 3 * ge(index, 15);
+    char::from(byte as u8)
+}
+
+/// Map a vowel to its index, returning the index and a mask that is `0xFF` if
+/// `vowel` is not a vowel.
+///
+/// Every vowel is compared so the running time does not depend on which
+/// vowel, if any, matches.
+#[inline]
+fn index_from_vowel(vowel: u8) -> (u8, u8) {
+    let mut index = 0;
+    let mut found = 0;
+    for (idx, &candidate) in (0_u8..).zip(VOWELS.iter()) {
+        let mask = eq(vowel, candidate);
+        index |= mask & idx;
+        found |= mask;
+    }
+    (index, !found)
+}
+
+/// Map a consonant to its index, returning the index and a mask that is
+/// `0xFF` if `consonant` is not a consonant.
+///
+/// Every consonant is compared so the running time does not depend on which
+/// consonant, if any, matches.
+#[inline]
+fn index_from_consonant(consonant: u8) -> (u8, u8) {
+    let mut index = 0;
+    let mut found = 0;
+    for (idx, &candidate) in (0_u8..).zip(CONSONANTS.iter()) {
+        let mask = eq(consonant, candidate);
+        index |= mask & idx;
+        found |= mask;
+    }
+    (index, !found)
+}
+
+/// Returns `0xFF` if `a == b` and `0` otherwise.
+#[inline]
+fn eq(a: u8, b: u8) -> u8 {
+    let diff = u16::from(a ^ b);
+    // `diff - 1` only borrows into the high byte when `diff` is zero.
+    (diff.wrapping_sub(1) >> 8) as u8
+}
+
+/// Returns `1` if `x >= k` and `0` otherwise, for `x` and `k` less than
+/// `2^15`.
+#[inline]
+fn ge(x: u16, k: u16) -> u16 {
+    // `k - 1 - x` only wraps around when `x >= k`.
+    (k.wrapping_sub(1).wrapping_sub(x) >> 15) & 1
+}
+
+/// `x / 6` for `x` less than `2^9`.
+#[inline]
+fn div6(x: u16) -> u16 {
+    // 171 / 1024 is a close enough approximation of 1 / 6 over the domain.
+    ((u32::from(x) * 171) >> 10) as u16
+}
+
+/// `x % 6` for `x` less than `2^9`.
+#[inline]
+fn mod6(x: u16) -> u16 {
+    x - 6 * div6(x)
+}
+
+/// `x % 36` for `x` less than `2^15`.
+#[inline]
+fn mod36(x: u16) -> u16 {
+    // 3641 / 131072 is a close enough approximation of 1 / 36 over the domain.
+    let quotient = ((u32::from(x) * 3641) >> 17) as u16;
+    x - 36 * quotient
+}
+
+#[cfg(test)]
+#[allow(clippy::non_ascii_literal)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{
+        consonant, decode, div6, encode, eq, ge, index_from_consonant, index_from_vowel, mod36,
+        mod6, vowel,
+    };
+    use crate::encode::{CONSONANTS, VOWELS};
+    use crate::DecodeError;
+
+    #[test]
+    fn arithmetic_helpers() {
+        for x in 0..512 {
+            assert_eq!(div6(x), x / 6);
+            assert_eq!(mod6(x), x % 6);
+        }
+        for x in 0..32768 {
+            assert_eq!(mod36(x), x % 36);
+        }
+        for x in 0..256 {
+            for k in 1..256 {
+                assert_eq!(ge(x, k), u16::from(x >= k));
+            }
+        }
+        for a in 0..=255 {
+            for b in 0..=255 {
+                assert_eq!(eq(a, b), if a == b { 0xFF } else { 0 });
+            }
+        }
+    }
+
+    #[test]
+    fn alphabet_mapping() {
+        for (idx, &byte) in (0..).zip(VOWELS.iter()) {
+            assert_eq!(vowel(idx), char::from(byte));
+        }
+        for (idx, &byte) in (0..).zip(CONSONANTS.iter()) {
+            assert_eq!(consonant(idx), char::from(byte));
+        }
+        for byte in 0..=255 {
+            let vowel = VOWELS.iter().position(|&v| v == byte);
+            let consonant = CONSONANTS.iter().position(|&c| c == byte);
+            match (vowel, index_from_vowel(byte)) {
+                (Some(idx), (index, 0)) => assert_eq!(usize::from(index), idx),
+                (None, (_, 0xFF)) => {}
+                (expected, actual) => panic!("{}: {:?} {:?}", byte, expected, actual),
+            }
+            match (consonant, index_from_consonant(byte)) {
+                (Some(idx), (index, 0)) => assert_eq!(usize::from(index), idx),
+                (None, (_, 0xFF)) => {}
+                (expected, actual) => panic!("{}: {:?} {:?}", byte, expected, actual),
+            }
+        }
+    }
+
+    #[test]
+    fn matches_encode_and_decode() {
+        let mut data = Vec::new();
+        for byte in 0..=255_u8 {
+            let encoded = encode(&data);
+            assert_eq!(encoded, crate::encode(&data));
+            assert_eq!(decode(&encoded), Ok(data.clone()));
+            data.push(byte.wrapping_mul(167));
+        }
+    }
+
+    #[test]
+    fn decode_error() {
+        let test_cases: [&[u8]; 12] = [
+            b"",
+            b"z",
+            b"xy",
+            b"yx",
+            b"xx",
+            "x💎🦀x".as_bytes(),
+            b"x789x",
+            b"xigak-nyryk-/umil-bosek-sonax",
+            b"xigak-nyryk-Humil-bosek-sonax",
+            b"xesefxdisofxgytufxkatofxmovifxbaxux",
+            b"xssef-disof-gytuf-katof-movif-baxux",
+            b"xeeef-disof-gytuf-katof-movif-baxux",
+        ];
+        for tc in &test_cases {
+            assert!(crate::decode(tc).is_err());
+            assert_eq!(decode(tc), Err(DecodeError::Corrupted));
+        }
+    }
+
+    #[test]
+    fn decode_checksum_mismatch() {
+        // Substitute every vowel in the final partial tuple.
+        for &vowel in &VOWELS {
+            let mut encoded = encode("1234567890").into_bytes();
+            let len = encoded.len();
+            for idx in &[len - 4, len - 2] {
+                let original = encoded[*idx];
+                encoded[*idx] = vowel;
+                assert_eq!(decode(&encoded).is_ok(), crate::decode(&encoded).is_ok());
+                encoded[*idx] = original;
+            }
+        }
+    }
+}
@@ -1,41 +1,146 @@
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+use crate::encode::{CONSONANTS, VOWELS};
 use crate::DecodeError;
 
 const HEADER: u8 = b'x';
 const TRAILER: u8 = b'x';
 
-// one `bool` for every byte. The positions that are set to `true` are the byte
-// values for characters in the alphabet:
-//
-// ```
-// const ALPHABET: [u8; 24] = *b"aeiouybcdfghklmnprstvzx-";
-// ```
-//
-// This table is generated with the following Ruby script:
-//
-// ```ruby
-// a = Array.new(256, 0)
-// bytes = "aeiouybcdfghklmnprstvzx-".split("").map(&:ord)
-// bytes.each {|b| a[b] = 1}
-// puts a.inspect
-// ```
-const ALPHABET_TABLE: [u8; 256] = [
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-];
+/// Marks table entries for vowels. The low bits hold the vowel's index.
+const VOWEL: u8 = 0x40;
+/// Marks table entries for consonants. The low bits hold the consonant's
+/// index.
+const CONSONANT: u8 = 0x80;
+/// Masks the class bits of a table entry.
+const CLASS: u8 = VOWEL | CONSONANT;
+/// The table entry for the `-` tuple separator and the `x` header, trailer,
+/// and checksum marker, which are in the alphabet but are neither vowels nor
+/// consonants.
+const MARKER: u8 = 0x20;
+/// The table entry for bytes outside of the encoding alphabet.
+const INVALID: u8 = 0xFF;
 
+/// Classification of every byte.
+///
+/// Each entry is one of:
+///
+/// - `VOWEL | index` for the 6 vowels.
+/// - `CONSONANT | index` for the 16 consonants.
+/// - `MARKER` for `-` and `x`.
+/// - `INVALID` for every byte outside of the 24 character encoding alphabet.
+///
+/// A single lookup validates the alphabet, distinguishes vowels from
+/// consonants, and yields the index used for decoding.
+static DECODE_TABLE: [u8; 256] = decode_table();
+
+const fn decode_table() -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    table[b'-' as usize] = MARKER;
+    table[b'x' as usize] = MARKER;
+    let mut index = 0;
+    while index < VOWELS.len() {
+        table[VOWELS[index] as usize] = VOWEL | index as u8;
+        index += 1;
+    }
+    let mut index = 0;
+    while index < CONSONANTS.len() {
+        table[CONSONANTS[index] as usize] = CONSONANT | index as u8;
+        index += 1;
+    }
+    table
+}
+
+#[cfg(feature = "alloc")]
 pub fn inner(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::with_capacity(decoded_capacity(encoded.len()));
+    inner_into(encoded, &mut decoded)?;
+    Ok(decoded)
+}
+
+/// An upper bound on the length of the decoded output of an `len` byte
+/// encoded input.
+///
+/// Reserving this capacity up front means the output buffer never reallocates
+/// during decoding, so no stale copies of the decoded bytes are left behind in
+/// freed memory.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decoded_capacity(len: usize) -> usize {
+    // Well-formed inputs have `6 * n + 5` bytes and decode to at most
+    // `2 * n + 1` bytes.
+    if len == 5 {
+        1
+    } else {
+        2 * ((len + 1) / 6)
+    }
+}
+
+/// Decode `encoded`, appending the decoded bytes to `decoded`.
+///
+/// On error, `decoded` may contain a partially decoded prefix.
+#[cfg(feature = "alloc")]
+pub fn inner_into(encoded: &[u8], decoded: &mut Vec<u8>) -> Result<(), DecodeError> {
+    decoded.reserve(decoded_capacity(encoded.len()));
+    inner_with(encoded, decoded)
+}
+
+/// A destination for decoded bytes.
+pub trait Sink {
+    fn push_byte(&mut self, byte: u8);
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for Vec<u8> {
+    #[inline]
+    fn push_byte(&mut self, byte: u8) {
+        self.push(byte);
+    }
+}
+
+/// Writes decoded bytes to the front of a slice.
+///
+/// Bytes that do not fit are dropped, but still counted in `len`, so callers
+/// can detect inputs that decode to more bytes than expected.
+#[derive(Debug)]
+struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Sink for SliceSink<'_> {
+    #[inline]
+    fn push_byte(&mut self, byte: u8) {
+        if let Some(slot) = self.buf.get_mut(self.len) {
+            *slot = byte;
+        }
+        self.len += 1;
+    }
+}
+
+/// Decode `encoded` into the front of `out`, returning the number of bytes
+/// written.
+///
+/// If the decoded output does not fit in `out`, `DecodeError::Corrupted` is
+/// returned. On error, `out` may contain a partially decoded prefix.
+pub fn inner_to_slice(encoded: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+    let mut sink = SliceSink { buf: out, len: 0 };
+    inner_with(encoded, &mut sink)?;
+    if sink.len <= sink.buf.len() {
+        Ok(sink.len)
+    } else {
+        Err(DecodeError::Corrupted)
+    }
+}
+
+/// Decode `encoded` into `decoded`.
+///
+/// On error, `decoded` may contain a partially decoded prefix.
+pub fn inner_with<S: Sink>(encoded: &[u8], decoded: &mut S) -> Result<(), DecodeError> {
     // `xexax` is the encoded representation of an empty byte string. Test for
     // it directly to short circuit.
     if encoded == b"xexax" {
-        return Ok(Vec::new());
+        return Ok(());
     }
     let enc = match encoded {
         [HEADER, enc @ .., TRAILER] => enc,
@@ -43,53 +148,84 @@ pub fn inner(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
         [.., TRAILER] => return Err(DecodeError::MalformedHeader),
         _ => return Err(DecodeError::Corrupted),
     };
-    // This validation step ensures that the encoded byte string only contains
-    // ASCII bytes in the 24 character encoding alphabet.
-    //
-    // Code below must still handle None results from `find_byte` because bytes
-    // may not be from the right subset of the alphabet, e.g. a vowel present
-    // when a consonant is expected.
-    if let Some((_, pos)) = enc
-        .iter()
-        .zip(1_usize..) // start `pos` at 1 because we stripped off a leading 'x'
-        .find(|(&byte, _)| ALPHABET_TABLE[usize::from(byte)] == 0)
-    {
-        return Err(DecodeError::InvalidByte(pos));
-    }
-    let mut decoded = {
-        let len = encoded.len();
-        Vec::with_capacity(if len == 5 { 1 } else { 2 * ((len + 1) / 6) })
-    };
+    decode_body(enc, decoded).map_err(|err| invalid_byte(enc).unwrap_or(err))
+}
+
+/// Decode the tuples between the header and trailer in a single pass.
+///
+/// Every byte is classified as it is decoded, so bytes outside of the
+/// encoding alphabet surface as whichever structural error they cause. The
+/// caller maps failures back to `InvalidByte` if there are any such bytes.
+fn decode_body<S: Sink>(enc: &[u8], decoded: &mut S) -> Result<(), DecodeError> {
     let mut checksum = 1_u8;
     let mut chunks = enc.chunks_exact(6);
-    while let Some(&[left, mid, right, up, b'-', down]) = chunks.next() {
-        let byte1 = decode_3_tuple(
-            index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?,
-            index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
-            index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?,
-            checksum,
-        )?;
-        let byte2 = decode_2_tuple(
-            index_from_consonant(up).ok_or(DecodeError::ExpectedConsonant)?,
-            index_from_consonant(down).ok_or(DecodeError::ExpectedConsonant)?,
-        );
-        checksum =
-            ((u16::from(checksum * 5) + (u16::from(byte1) * 7) + u16::from(byte2)) % 36) as u8;
-        decoded.push(byte1);
-        decoded.push(byte2);
+    for chunk in &mut chunks {
+        let (byte1, byte2) = decode_tuple(chunk, checksum).map_err(|err| match err {
+            // A misaligned tuple in an input of invalid length is corrupt,
+            // like the length itself.
+            DecodeError::ChecksumMismatch if enc.len() % 6 != 3 => DecodeError::Corrupted,
+            err => err,
+        })?;
+        checksum = next_checksum(checksum, byte1, byte2);
+        decoded.push_byte(byte1);
+        decoded.push_byte(byte2);
+    }
+    if let Some(byte) = decode_tail(chunks.remainder(), checksum)? {
+        decoded.push_byte(byte);
     }
-    if let [left, mid, right] = *chunks.remainder() {
+    Ok(())
+}
+
+/// Find the first byte outside of the encoding alphabet.
+///
+/// Any such byte takes precedence over structural errors. This scan only runs
+/// once decoding has already failed.
+#[cold]
+fn invalid_byte(enc: &[u8]) -> Option<DecodeError> {
+    enc.iter()
+        .zip(1_usize..) // start `pos` at 1 because we stripped off a leading 'x'
+        .find(|(&byte, _)| DECODE_TABLE[usize::from(byte)] == INVALID)
+        .map(|(_, pos)| DecodeError::InvalidByte(pos))
+}
+
+/// Decode one 6 byte tuple at `checksum` to the pair of bytes it encodes.
+#[inline]
+pub fn decode_tuple(chunk: &[u8], checksum: u8) -> Result<(u8, u8), DecodeError> {
+    // A tuple separator other than `-` means the tuples are misaligned and the
+    // checksum cannot be validated. Callers report this as `Corrupted` if the
+    // input length is invalid.
+    let (left, mid, right, up, down) = match *chunk {
+        [left, mid, right, up, b'-', down] => (left, mid, right, up, down),
+        _ => return Err(DecodeError::ChecksumMismatch),
+    };
+    let byte1 = decode_3_tuple(
+        index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?,
+        index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
+        index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?,
+        checksum,
+    )?;
+    let byte2 = decode_2_tuple(
+        index_from_consonant(up).ok_or(DecodeError::ExpectedConsonant)?,
+        index_from_consonant(down).ok_or(DecodeError::ExpectedConsonant)?,
+    );
+    Ok((byte1, byte2))
+}
+
+/// Decode the final partial tuple, which is either a checksum-only tuple or
+/// encodes one trailing odd byte.
+#[inline]
+pub fn decode_tail(tail: &[u8], checksum: u8) -> Result<Option<u8>, DecodeError> {
+    if let [left, mid, right] = *tail {
         let a = index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?;
         let c = index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?;
 
         match mid {
             b'x' if a != checksum % 6 || c != checksum / 6 => Err(DecodeError::ChecksumMismatch),
-            b'x' => Ok(decoded),
+            b'x' => Ok(None),
             _ => {
                 let b = index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?;
                 let byte = decode_3_tuple(a, b, c, checksum)?;
-                decoded.push(byte);
-                Ok(decoded)
+                Ok(Some(byte))
             }
         }
     } else {
@@ -97,57 +233,64 @@ pub fn inner(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
     }
 }
 
+/// Advance the checksum past the tuple that decoded to `byte1` and `byte2`.
+#[inline]
+pub fn next_checksum(checksum: u8, byte1: u8, byte2: u8) -> u8 {
+    ((u16::from(checksum * 5) + (u16::from(byte1) * 7) + u16::from(byte2)) % 36) as u8
+}
+
 #[inline]
 fn index_from_consonant(consonant: u8) -> Option<u8> {
-    let index = match consonant {
-        b'b' => 0,
-        b'c' => 1,
-        b'd' => 2,
-        b'f' => 3,
-        b'g' => 4,
-        b'h' => 5,
-        b'k' => 6,
-        b'l' => 7,
-        b'm' => 8,
-        b'n' => 9,
-        b'p' => 10,
-        b'r' => 11,
-        b's' => 12,
-        b't' => 13,
-        b'v' => 14,
-        b'z' => 15,
-        _ => return None,
-    };
-    Some(index)
+    let entry = DECODE_TABLE[usize::from(consonant)];
+    if entry & CLASS == CONSONANT {
+        Some(entry & !CONSONANT)
+    } else {
+        None
+    }
 }
 
 #[inline]
 fn index_from_vowel(vowel: u8) -> Option<u8> {
-    let index = match vowel {
-        b'a' => 0,
-        b'e' => 1,
-        b'i' => 2,
-        b'o' => 3,
-        b'u' => 4,
-        b'y' => 5,
-        _ => return None,
-    };
-    Some(index)
+    let entry = DECODE_TABLE[usize::from(vowel)];
+    if entry & CLASS == VOWEL {
+        Some(entry & !VOWEL)
+    } else {
+        None
+    }
+}
+
+/// Undoes the checksum skew of the first vowel of a tuple.
+///
+/// Indexed by `checksum * 6 + index`, the entry is `(index - checksum) mod 6`.
+static UNSKEW_HIGH: [u8; 36 * 6] = unskew_table(false);
+/// Undoes the checksum skew of the last vowel of a tuple.
+///
+/// Indexed by `checksum * 6 + index`, the entry is
+/// `(index - checksum / 6) mod 6`.
+static UNSKEW_LOW: [u8; 36 * 6] = unskew_table(true);
+
+const fn unskew_table(low: bool) -> [u8; 36 * 6] {
+    let mut table = [0; 36 * 6];
+    let mut checksum = 0;
+    while checksum < 36 {
+        let skew = if low { checksum / 6 } else { checksum % 6 };
+        let mut index = 0;
+        while index < 6 {
+            table[checksum * 6 + index] = ((index + 6 - skew) % 6) as u8;
+            index += 1;
+        }
+        checksum += 1;
+    }
+    table
 }
 
 #[inline]
 fn decode_3_tuple(byte1: u8, byte2: u8, byte3: u8, checksum: u8) -> Result<u8, DecodeError> {
-    // Will not overflow since:
-    // - `byte1` is guaranteed to be ASCII or < 128.
-    // Will not underflow since:
-    // - 6 - (checksum % 6) > 0
-    let high = (byte1 + 6 - (checksum % 6)) % 6;
+    // `checksum` is always less than 36 and vowel indexes are less than 6.
+    let row = usize::from(checksum) * 6;
+    let high = UNSKEW_HIGH[row + usize::from(byte1)];
     let mid = byte2;
-    // Will not overflow since:
-    // - `byte3` is guaranteed to be ASCII or < 128.
-    // Will not underflow since:
-    // - 6 - ((checksum / 6) % 6) > 0
-    let low = (byte3 + 6 - ((checksum / 6) % 6)) % 6;
+    let low = UNSKEW_LOW[row + usize::from(byte3)];
     if high >= 4 || low >= 4 {
         Err(DecodeError::Corrupted)
     } else {
@@ -159,3 +302,27 @@ fn decode_3_tuple(byte1: u8, byte2: u8, byte3: u8, checksum: u8) -> Result<u8, D
 fn decode_2_tuple(byte1: u8, byte2: u8) -> u8 {
     (byte1 << 4) | byte2
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{index_from_consonant, index_from_vowel, DECODE_TABLE, INVALID};
+    use crate::encode::{CONSONANTS, VOWELS};
+
+    #[test]
+    fn table_classifies_alphabet() {
+        for (index, &vowel) in (0_u8..).zip(VOWELS.iter()) {
+            assert_eq!(index_from_vowel(vowel), Some(index));
+            assert_eq!(index_from_consonant(vowel), None);
+        }
+        for (index, &consonant) in (0_u8..).zip(CONSONANTS.iter()) {
+            assert_eq!(index_from_consonant(consonant), Some(index));
+            assert_eq!(index_from_vowel(consonant), None);
+        }
+        for &marker in b"x-" {
+            assert_eq!(index_from_vowel(marker), None);
+            assert_eq!(index_from_consonant(marker), None);
+        }
+        let valid = DECODE_TABLE.iter().filter(|&&entry| entry != INVALID);
+        assert_eq!(valid.count(), 24);
+    }
+}
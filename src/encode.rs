@@ -1,92 +1,295 @@
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
 
-const VOWELS: [u8; 6] = *b"aeiouy";
-const CONSONANTS: [u8; 16] = *b"bcdfghklmnprstvz";
-const HEADER: &str = "x";
-const TRAILER: &str = "x";
-const SEPARATOR: &str = "-";
-const MID: &str = "x";
+pub const VOWELS: [u8; 6] = *b"aeiouy";
+pub const CONSONANTS: [u8; 16] = *b"bcdfghklmnprstvz";
+pub const HEADER: u8 = b'x';
+const TRAILER: u8 = b'x';
+const SEPARATOR: u8 = b'-';
+const MID: u8 = b'x';
 
+/// The number of pairs of input bytes that `push_tuples` encodes per block.
+#[cfg(feature = "alloc")]
+const BLOCK_PAIRS: usize = 256;
+
+/// The vowel-consonant-vowel half of a tuple for every checksum and byte.
+///
+/// Indexed by checksum and then by byte. This encodes the first byte of every
+/// full tuple and the odd trailing byte of odd-length inputs.
+static VOWEL_HALVES: [[[u8; 3]; 256]; 36] = vowel_halves();
+
+/// The consonant-separator-consonant half of a tuple for every byte.
+///
+/// This encodes the second byte of every full tuple and does not depend on the
+/// checksum.
+static CONSONANT_HALVES: [[u8; 3]; 256] = consonant_halves();
+
+/// The checksum update `(5 * checksum + sum) % 36` for every checksum and every
+/// `sum = (7 * left + right) % 36` of a pair of bytes.
+///
+/// `sum` does not depend on the checksum, so looking up the next checksum in
+/// this table keeps the dependency chain between tuples to a single load.
+static CHECKSUM_STEPS: [[u8; 36]; 36] = checksum_steps();
+
+// This function is only evaluated at compile time to initialize
+// `VOWEL_HALVES`, so the table is never built on the stack.
+#[allow(clippy::large_stack_arrays)]
+const fn vowel_halves() -> [[[u8; 3]; 256]; 36] {
+    let mut table = [[[0; 3]; 256]; 36];
+    let mut checksum = 0;
+    while checksum < 36 {
+        let mut byte = 0;
+        while byte < 256 {
+            table[checksum][byte] = vowel_half(checksum, byte);
+            byte += 1;
+        }
+        checksum += 1;
+    }
+    table
+}
+
+const fn consonant_halves() -> [[u8; 3]; 256] {
+    let mut table = [[0; 3]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = consonant_half(byte);
+        byte += 1;
+    }
+    table
+}
+
+/// Encode `byte` as the vowel-consonant-vowel half of a tuple.
+const fn vowel_half(checksum: usize, byte: usize) -> [u8; 3] {
+    let a = (((byte >> 6) & 3) + checksum) % 6;
+    let b = (byte >> 2) & 15;
+    let c = ((byte & 3) + checksum / 6) % 6;
+    [VOWELS[a], CONSONANTS[b], VOWELS[c]]
+}
+
+/// Encode `byte` as the consonant-separator-consonant half of a tuple.
+const fn consonant_half(byte: usize) -> [u8; 3] {
+    let d = (byte >> 4) & 15;
+    let e = byte & 15;
+    [CONSONANTS[d], SEPARATOR, CONSONANTS[e]]
+}
+
+const fn checksum_steps() -> [[u8; 36]; 36] {
+    let mut table = [[0; 36]; 36];
+    let mut checksum = 0;
+    while checksum < 36 {
+        let mut sum = 0;
+        while sum < 36 {
+            table[checksum][sum] = ((checksum * 5 + sum) % 36) as u8;
+            sum += 1;
+        }
+        checksum += 1;
+    }
+    table
+}
+
+/// The length of the encoding of `len` bytes of data.
+///
+/// Every pair of input bytes encodes to a 6 byte tuple. The header, final
+/// partial tuple, and trailer add 5 more bytes.
+#[inline]
+pub const fn encoded_len(len: usize) -> Option<usize> {
+    match (len / 2).checked_mul(6) {
+        Some(tuples) => tuples.checked_add(5),
+        None => None,
+    }
+}
+
+#[cfg(feature = "alloc")]
 #[must_use]
 pub fn inner(data: &[u8]) -> String {
-    if data.is_empty() {
-        return String::from("xexax");
+    let len = encoded_len(data.len()).expect("encoded length overflows usize");
+    let mut encoded = vec![0; len];
+    encode_exact(data, &mut encoded);
+    String::from_utf8(encoded).expect("Bubble Babble encodings are ASCII")
+}
+
+/// Encode `data`, appending the encoding to `encoded`.
+#[cfg(feature = "alloc")]
+pub fn inner_into(data: &[u8], encoded: &mut String) {
+    let len = encoded_len(data.len()).expect("encoded length overflows usize");
+    encoded.reserve(len);
+    let pairs = data.len() / 2 * 2;
+    encoded.push(char::from(HEADER));
+    let checksum = push_tuples(&data[..pairs], 1, encoded);
+    push_tail(&data[pairs..], checksum, encoded);
+}
+
+/// Encode each pair of bytes in `pairs` starting from `checksum`, appending
+/// the tuples to `encoded`, and return the checksum that follows the last
+/// tuple.
+///
+/// A trailing odd byte in `pairs` is ignored.
+#[cfg(feature = "alloc")]
+pub fn push_tuples(pairs: &[u8], mut checksum: u8, encoded: &mut String) -> u8 {
+    // Encode through a stack buffer and append one block at a time. Only the
+    // newly encoded bytes are checked for UTF-8, never the existing contents
+    // of `encoded`.
+    let pairs = &pairs[..pairs.len() / 2 * 2];
+    let mut buf = [0; 6 * BLOCK_PAIRS];
+    for block in pairs.chunks(2 * BLOCK_PAIRS) {
+        let out = &mut buf[..block.len() * 3];
+        checksum = encode_tuples(block, checksum, out);
+        encoded.push_str(ascii(out));
+    }
+    // Do not leave a copy of the last block on the stack.
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut buf);
+    checksum
+}
+
+/// Append the final partial tuple and the trailer to `encoded`.
+#[cfg(feature = "alloc")]
+pub fn push_tail(remainder: &[u8], checksum: u8, encoded: &mut String) {
+    let mut tail = [0; 4];
+    encode_tail(remainder, checksum, &mut tail);
+    encoded.push_str(ascii(&tail));
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut tail);
+}
+
+#[inline]
+pub fn ascii(encoded: &[u8]) -> &str {
+    core::str::from_utf8(encoded).expect("Bubble Babble encodings are ASCII")
+}
+
+/// Encode `data` into the front of `out`, returning the number of bytes
+/// written, or `None` if `out` is too short to hold the encoding.
+pub fn inner_to_slice(data: &[u8], out: &mut [u8]) -> Option<usize> {
+    let len = encoded_len(data.len())?;
+    let out = out.get_mut(..len)?;
+    encode_exact(data, out);
+    Some(len)
+}
+
+/// Encode `data` into `out`, which must be exactly `encoded_len(data.len())`
+/// bytes long.
+pub fn encode_exact(data: &[u8], out: &mut [u8]) {
+    let pairs = data.len() / 2 * 2;
+    let (header, out) = out.split_at_mut(1);
+    let (tuples, tail) = out.split_at_mut(pairs * 3);
+    header[0] = HEADER;
+    let checksum = encode_tuples(&data[..pairs], 1, tuples);
+    encode_tail(&data[pairs..], checksum, tail);
+}
+
+/// Encode `data` into an `M` byte array in a const context.
+///
+/// Statics cannot be read from a `const fn`, so this computes every tuple from
+/// `VOWELS` and `CONSONANTS` instead of looking it up in the encoding tables.
+/// `M` must be `encoded_len(N)`.
+pub const fn encode_const<const N: usize, const M: usize>(data: &[u8; N]) -> [u8; M] {
+    let mut out = [0; M];
+    out[0] = HEADER;
+    let mut checksum = 1;
+    let mut pos = 0;
+    while pos + 1 < N {
+        let left = data[pos] as usize;
+        let right = data[pos + 1] as usize;
+        let vowels = vowel_half(checksum, left);
+        let consonants = consonant_half(right);
+        let tuple = 1 + pos * 3;
+        let mut i = 0;
+        while i < 3 {
+            out[tuple + i] = vowels[i];
+            out[tuple + 3 + i] = consonants[i];
+            i += 1;
+        }
+        checksum = (checksum * 5 + left * 7 + right) % 36;
+        pos += 2;
     }
+    let partial = if pos < N {
+        vowel_half(checksum, data[pos] as usize)
+    } else {
+        [VOWELS[checksum % 6], MID, VOWELS[checksum / 6]]
+    };
+    let tail = 1 + pos * 3;
+    out[tail] = partial[0];
+    out[tail + 1] = partial[1];
+    out[tail + 2] = partial[2];
+    out[tail + 3] = TRAILER;
+    out
+}
 
-    let mut encoded = String::with_capacity(6 * (data.len() / 2) + 3 + 2);
-    encoded.push_str(HEADER);
-    let mut checksum = 1_u8;
-    let mut chunks = data.chunks_exact(2);
-    while let Some(&[left, right]) = chunks.next() {
-        odd_partial(left, checksum, &mut encoded);
-        let d = (right >> 4) & 15;
-        let e = right & 15;
+/// Encode each pair of bytes in `pairs` as a 6 byte tuple into `out`, starting
+/// from `checksum`, and return the checksum that follows the last tuple.
+///
+/// `out` must be exactly 3 times as long as `pairs`. A trailing odd byte in
+/// `pairs` is ignored.
+pub fn encode_tuples(pairs: &[u8], mut checksum: u8, out: &mut [u8]) -> u8 {
+    for (tuple, pair) in out.chunks_exact_mut(6).zip(pairs.chunks_exact(2)) {
+        let (left, right) = (pair[0], pair[1]);
         // Panic safety:
         //
-        // - `d` is constructed with a mask of `0b1111`.
-        // - `CONSONANTS` is a fixed size array with 16 elements.
-        // - Maximum value of `d` is 15.
-        encoded.push(CONSONANTS[d as usize].into());
-        encoded.push_str(SEPARATOR);
+        // - `checksum` is constructed with mod 36.
+        // - `VOWEL_HALVES` is a fixed size array with 36 elements.
+        // - Each table row is a fixed size array with 256 elements, one for
+        //   every `u8`.
+        tuple[..3].copy_from_slice(&VOWEL_HALVES[usize::from(checksum)][usize::from(left)]);
+        tuple[3..].copy_from_slice(&CONSONANT_HALVES[usize::from(right)]);
+        let sum = (u16::from(left) * 7 + u16::from(right)) % 36;
         // Panic safety:
         //
-        // - `e` is constructed with a mask of `0b1111`.
-        // - `CONSONANTS` is a fixed size array with 16 elements.
-        // - Maximum value of `e` is 15.
-        encoded.push(CONSONANTS[e as usize].into());
-        checksum = ((u16::from(checksum * 5) + u16::from(left) * 7 + u16::from(right)) % 36) as u8;
+        // - `checksum` and `sum` are constructed with mod 36.
+        // - `CHECKSUM_STEPS` is a 36 by 36 fixed size array.
+        checksum = CHECKSUM_STEPS[usize::from(checksum)][usize::from(sum)];
     }
-    if let [byte] = chunks.remainder() {
-        odd_partial(*byte, checksum, &mut encoded);
-    } else {
-        even_partial(checksum, &mut encoded);
-    }
-    encoded.push_str(TRAILER);
-    encoded
+    checksum
 }
 
-#[inline]
-fn odd_partial(raw_byte: u8, checksum: u8, buf: &mut String) {
-    let a = (((raw_byte >> 6) & 3) + checksum) % 6;
-    let b = (raw_byte >> 2) & 15;
-    let c = ((raw_byte & 3) + checksum / 6) % 6;
-    // Panic safety:
-    //
-    // - `a` is constructed with mod 6.
-    // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `a` is 5.
-    buf.push(VOWELS[a as usize].into());
-    // Panic safety:
-    //
-    // - `b` is constructed with a mask of `0b1111`.
-    // - `CONSONANTS` is a fixed size array with 16 elements.
-    // - Maximum value of `e` is 15.
-    buf.push(CONSONANTS[b as usize].into());
-    // Panic safety:
-    //
-    // - `c` is constructed with mod 6.
-    // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `c` is 5.
-    buf.push(VOWELS[c as usize].into());
+/// Encode the final partial tuple and the trailer into the first 4 bytes of
+/// `out`.
+///
+/// `remainder` is the odd byte left over after all pairs are encoded, or empty
+/// if the input has even length.
+pub fn encode_tail(remainder: &[u8], checksum: u8, out: &mut [u8]) {
+    let partial = if let [byte] = *remainder {
+        VOWEL_HALVES[usize::from(checksum)][usize::from(byte)]
+    } else {
+        let a = checksum % 6;
+        let c = checksum / 6;
+        // Panic safety:
+        //
+        // - `a` is constructed with mod 6.
+        // - `c` is constructed with divide by 6 and the maximum value of
+        //   `checksum` is 35.
+        // - `VOWELS` is a fixed size array with 6 elements.
+        [VOWELS[usize::from(a)], MID, VOWELS[usize::from(c)]]
+    };
+    out[..3].copy_from_slice(&partial);
+    out[3] = TRAILER;
 }
 
-#[inline]
-fn even_partial(checksum: u8, buf: &mut String) {
-    let a = checksum % 6;
-    // let b = 16;
-    let c = checksum / 6;
-    // Panic safety:
-    //
-    // - `a` is constructed with mod 6.
-    // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `a` is 5.
-    buf.push(VOWELS[a as usize].into());
-    buf.push_str(MID);
-    // Panic safety:
-    //
-    // - `c` is constructed with divide by 6.
-    // - Maximum value of `checksum` is 36 -- see `encode` loop.
-    // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `c` is 5.
-    buf.push(VOWELS[c as usize].into());
+#[cfg(test)]
+mod tests {
+    use super::{CHECKSUM_STEPS, CONSONANTS, CONSONANT_HALVES, VOWELS, VOWEL_HALVES};
+
+    #[test]
+    fn tables_match_alphabet() {
+        assert_eq!(VOWEL_HALVES[0][0], *b"aba");
+        assert_eq!(VOWEL_HALVES[1][0], *b"eba");
+        assert_eq!(VOWEL_HALVES[6][0], *b"abe");
+        assert_eq!(VOWEL_HALVES[35][255], *b"izi");
+        for row in &VOWEL_HALVES {
+            for half in row {
+                assert!(VOWELS.contains(&half[0]));
+                assert!(CONSONANTS.contains(&half[1]));
+                assert!(VOWELS.contains(&half[2]));
+            }
+        }
+        assert_eq!(CONSONANT_HALVES[0], *b"b-b");
+        assert_eq!(CONSONANT_HALVES[0x1f], *b"c-z");
+        assert_eq!(CONSONANT_HALVES[255], *b"z-z");
+        for (checksum, row) in CHECKSUM_STEPS.iter().enumerate() {
+            for (sum, &next) in row.iter().enumerate() {
+                assert_eq!(usize::from(next), (checksum * 5 + sum) % 36);
+            }
+        }
+    }
 }
@@ -33,19 +33,23 @@
 //! You can encode binary data by calling [`encode`](encode()):
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! let encoded = boba::encode("Pineapple");
 //! assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+//! # }
 //! ```
 //!
 //! Decoding binary data is done by calling [`decode`](decode()):
 //!
 //! ```
 //! # use boba::DecodeError;
+//! # #[cfg(feature = "alloc")]
 //! # fn example() -> Result<(), DecodeError> {
 //! let decoded = boba::decode("xexax")?;
 //! assert_eq!(decoded, vec![]);
 //! # Ok(())
 //! # }
+//! # #[cfg(feature = "alloc")]
 //! # example().unwrap();
 //! ```
 //!
@@ -54,23 +58,114 @@
 //! emoji will fail.
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use boba::DecodeError;
 //! let decoded = boba::decode("x🦀x");
 //! // The `DecodeError` contains the offset of the first invalid byte.
 //! assert_eq!(decoded, Err(DecodeError::InvalidByte(1)));
+//! # }
+//! ```
+//!
+//! When encoding secret material, use the constant-time encoder and decoder in
+//! the [`ct`] module instead.
+//!
+//! Bubble Babble encodings embedded in larger text, like log files, can be
+//! found with [`scan`](scan()):
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! let log = "accepted key xigak-nyryk-humil-bosek-sonax";
+//! let found = boba::scan(log).next().unwrap();
+//! assert_eq!(found.decoded(), b"Pineapple");
+//! # }
+//! ```
+//!
+//! To encode many short inputs without allocating for each one, append to a
+//! reused buffer with [`encode_into`] or collect the encodings into a
+//! [`BatchEncoder`]. To encode input that arrives in pieces, like blocks of a
+//! large file, use an [`Encoder`].
+//!
+//! Fixed-size inputs, like digests, can be encoded to an [`EncodedArray`] and
+//! decoded back to an array without allocating:
+//!
 //! ```
+//! # use boba::DecodeError;
+//! # fn example() -> Result<(), DecodeError> {
+//! let digest = [0xa5; 32];
+//! let encoded: boba::EncodedArray<{ boba::encoded_len(32) }> = boba::encode_array(&digest);
+//! let decoded: [u8; 32] = boba::decode_array(encoded)?;
+//! assert_eq!(decoded, digest);
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+//!
+//! Encodings of constants, like well-known fingerprints, can be computed at
+//! compile time with [`const_encode`].
 //!
 //! # Crate Features
 //!
-//! Boba is `no_std` compatible with a required dependency on the [`alloc`]
+//! Boba is `no_std` compatible with an optional dependency on the [`alloc`]
 //! crate.
 //!
-//! Boba has several Cargo features, all of which are enabled by default:
+//! Boba has several Cargo features. **std** and **alloc** are enabled by
+//! default:
 //!
 //! - **std** - Adds a dependency on [`std`], the Rust Standard Library. This
 //!   feature enables [`std::error::Error`] implementations on error types in
 //!   this crate. Enabling the **std** feature also enables the **alloc**
 //!   feature.
+//! - **alloc** - Adds a dependency on [`alloc`], the Rust allocation library.
+//!   This feature enables the functions that encode to a [`String`] and
+//!   decode to a [`Vec<u8>`](Vec), along with the [`ct`] module,
+//!   [`BatchEncoder`], [`Encoder`], [`scan`](scan()), and
+//!   [`split_concatenated`]. Without it, fixed-size arrays, slices, and
+//!   fixed-capacity containers can still be encoded and decoded.
+//! - **ssh** - Adds the [`ssh`] module for computing the Bubble Babble
+//!   fingerprints of OpenSSH public keys printed by `ssh-keygen -B`. This
+//!   feature adds dependencies on [`base64`] and [`sha1`].
+//! - **x509** - Adds the [`x509`] module for computing SHA-256 fingerprints of
+//!   X.509 certificates and their public keys. This feature adds dependencies
+//!   on [`sha2`] and [`x509-cert`].
+//! - **pgp** - Adds the [`pgp`] module for computing fingerprints of OpenPGP
+//!   keys. This feature adds dependencies on [`base64`], [`sha1`], and
+//!   [`sha2`].
+//! - **zeroize** - Adds [`encode_zeroizing`] and [`decode_zeroizing`], which
+//!   return buffers that are wiped when dropped. This feature adds a
+//!   dependency on [`zeroize`].
+//! - **subtle** - Adds [`ct_eq_encoded`] and [`ct_eq_bytes`] for comparing
+//!   encodings in constant time. This feature adds a dependency on [`subtle`].
+//! - **serde** - Adds the [`boba::serde`] module for serializing bytes as
+//!   Bubble Babble strings in human-readable formats. This feature adds a
+//!   dependency on [`serde`].
+//! - **`serde_with`** - Adds the `BubbleBabble` adapter to the
+//!   [`boba::serde`] module for use with [`serde_with`]. This feature requires
+//!   the **serde** feature and adds a dependency on [`serde_with`].
+//! - **clap** - Adds the [`boba::clap`] module with a value parser for
+//!   decoding Bubble Babble command line arguments. This feature adds
+//!   dependencies on [`std`] and [`clap`].
+//! - **transcode** - Adds the [`transcode`] module for converting between hex,
+//!   base64, and Bubble Babble. This feature adds a dependency on [`base64`].
+//! - **rayon** - Adds [`par_encode`] and [`par_decode`] for encoding and
+//!   decoding large inputs on multiple threads. This feature adds a dependency
+//!   on [`rayon`].
+//! - **heapless** - Adds [`encode_heapless`] and [`decode_heapless`] for
+//!   encoding and decoding into fixed-capacity containers without
+//!   allocating. This feature adds a dependency on [`heapless`].
+//! - **arrayvec** - Adds [`encode_arrayvec`] and [`decode_arrayvec`] for
+//!   encoding and decoding into fixed-capacity containers without
+//!   allocating. This feature adds a dependency on [`arrayvec`].
+//! - **bytes** - Adds [`encode_buf`] and [`decode_into_buf`] for encoding from
+//!   any [`Buf`], including non-contiguous chains, and decoding into any
+//!   [`BufMut`]. This feature adds a dependency on [`bytes`].
+//! - **macros** - Adds the [`bubblebabble!`] macro for decoding Bubble Babble
+//!   string literals at compile time. This feature adds a dependency on
+//!   [`boba-macros`].
+//!
+//! The APIs added by the **zeroize**, **subtle**, **serde**, **serde_with**,
+//! **clap**, and **rayon** features allocate. Enabling any of these features
+//! without the **alloc** feature fails to compile. [`encode_buf`] also
+//! requires the **alloc** feature.
 //!
 #![cfg_attr(
     not(feature = "std"),
@@ -80,22 +175,229 @@
     not(feature = "std"),
     doc = "[`std::error::Error`]: https://doc.rust-lang.org/stable/std/error/trait.Error.html"
 )]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`alloc`]: https://doc.rust-lang.org/stable/alloc/index.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`String`]: https://doc.rust-lang.org/stable/alloc/string/struct.String.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`Vec`]: https://doc.rust-lang.org/stable/alloc/vec/struct.Vec.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`ct`]: https://docs.rs/boba/latest/boba/ct/index.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`BatchEncoder`]: https://docs.rs/boba/latest/boba/struct.BatchEncoder.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`Encoder`]: https://docs.rs/boba/latest/boba/struct.Encoder.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`encode_into`]: https://docs.rs/boba/latest/boba/fn.encode_into.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`split_concatenated`]: https://docs.rs/boba/latest/boba/fn.split_concatenated.html"
+)]
+#![cfg_attr(
+    not(feature = "ssh"),
+    doc = "[`ssh`]: https://docs.rs/boba/latest/boba/ssh/index.html"
+)]
+#![cfg_attr(
+    not(feature = "pgp"),
+    doc = "[`pgp`]: https://docs.rs/boba/latest/boba/pgp/index.html"
+)]
+#![cfg_attr(
+    not(all(feature = "rayon", feature = "alloc")),
+    doc = "[`par_encode`]: https://docs.rs/boba/latest/boba/fn.par_encode.html"
+)]
+#![cfg_attr(
+    not(all(feature = "rayon", feature = "alloc")),
+    doc = "[`par_decode`]: https://docs.rs/boba/latest/boba/fn.par_decode.html"
+)]
+#![cfg_attr(
+    not(feature = "heapless"),
+    doc = "[`encode_heapless`]: https://docs.rs/boba/latest/boba/fn.encode_heapless.html"
+)]
+#![cfg_attr(
+    not(feature = "heapless"),
+    doc = "[`decode_heapless`]: https://docs.rs/boba/latest/boba/fn.decode_heapless.html"
+)]
+#![cfg_attr(
+    not(feature = "arrayvec"),
+    doc = "[`encode_arrayvec`]: https://docs.rs/boba/latest/boba/fn.encode_arrayvec.html"
+)]
+#![cfg_attr(
+    not(feature = "arrayvec"),
+    doc = "[`decode_arrayvec`]: https://docs.rs/boba/latest/boba/fn.decode_arrayvec.html"
+)]
+#![cfg_attr(
+    not(all(feature = "bytes", feature = "alloc")),
+    doc = "[`encode_buf`]: https://docs.rs/boba/latest/boba/fn.encode_buf.html"
+)]
+#![cfg_attr(
+    not(feature = "bytes"),
+    doc = "[`decode_into_buf`]: https://docs.rs/boba/latest/boba/fn.decode_into_buf.html"
+)]
+#![cfg_attr(
+    not(feature = "macros"),
+    doc = "[`bubblebabble!`]: https://docs.rs/boba/latest/boba/macro.bubblebabble.html"
+)]
+#![cfg_attr(
+    not(all(feature = "zeroize", feature = "alloc")),
+    doc = "[`encode_zeroizing`]: https://docs.rs/boba/latest/boba/fn.encode_zeroizing.html"
+)]
+#![cfg_attr(
+    not(all(feature = "zeroize", feature = "alloc")),
+    doc = "[`decode_zeroizing`]: https://docs.rs/boba/latest/boba/fn.decode_zeroizing.html"
+)]
+#![cfg_attr(
+    not(all(feature = "subtle", feature = "alloc")),
+    doc = "[`ct_eq_encoded`]: https://docs.rs/boba/latest/boba/fn.ct_eq_encoded.html"
+)]
+#![cfg_attr(
+    not(all(feature = "subtle", feature = "alloc")),
+    doc = "[`ct_eq_bytes`]: https://docs.rs/boba/latest/boba/fn.ct_eq_bytes.html"
+)]
+#![cfg_attr(
+    all(feature = "serde", feature = "alloc"),
+    doc = "[`boba::serde`]: crate::serde"
+)]
+#![cfg_attr(
+    not(all(feature = "serde", feature = "alloc")),
+    doc = "[`boba::serde`]: https://docs.rs/boba/latest/boba/serde/index.html"
+)]
+#![cfg_attr(
+    not(all(feature = "clap", feature = "alloc")),
+    doc = "[`boba::clap`]: https://docs.rs/boba/latest/boba/clap/index.html"
+)]
+#![cfg_attr(
+    not(feature = "transcode"),
+    doc = "[`transcode`]: https://docs.rs/boba/latest/boba/transcode/index.html"
+)]
+#![cfg_attr(
+    not(feature = "x509"),
+    doc = "[`x509`]: https://docs.rs/boba/latest/boba/x509/index.html"
+)]
+//! [`arrayvec`]: https://crates.io/crates/arrayvec
+//! [`base64`]: https://crates.io/crates/base64
+//! [`boba-macros`]: https://crates.io/crates/boba-macros
+//! [`Buf`]: https://docs.rs/bytes/1/bytes/trait.Buf.html
+//! [`BufMut`]: https://docs.rs/bytes/1/bytes/trait.BufMut.html
+//! [`bytes`]: https://crates.io/crates/bytes
+//! [`clap`]: https://crates.io/crates/clap
+//! [`heapless`]: https://crates.io/crates/heapless
+//! [`rayon`]: https://crates.io/crates/rayon
+//! [`sha1`]: https://crates.io/crates/sha1
+//! [`serde`]: https://crates.io/crates/serde
+//! [`serde_with`]: https://crates.io/crates/serde_with
+//! [`sha2`]: https://crates.io/crates/sha2
+//! [`subtle`]: https://crates.io/crates/subtle
+//! [`x509-cert`]: https://crates.io/crates/x509-cert
+//! [`zeroize`]: https://crates.io/crates/zeroize
 //! [perl-bubblebabble]: https://metacpan.org/pod/Digest::BubbleBabble
 //! [ruby-bubblebabble]: https://ruby-doc.org/stdlib-3.1.1/libdoc/digest/rdoc/Digest.html#method-c-bubblebabble
 
 #![no_std]
 #![doc(html_root_url = "https://docs.rs/boba/5.0.0")]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "clap"))]
 extern crate std;
 
+// Features named after optional dependencies cannot also enable `alloc`
+// without the `dep:` syntax, which requires a newer Rust than the MSRV. Fail
+// the build rather than silently leaving out the APIs they add.
+#[cfg(all(feature = "zeroize", not(feature = "alloc")))]
+compile_error!("the `zeroize` feature requires the `alloc` feature");
+#[cfg(all(feature = "subtle", not(feature = "alloc")))]
+compile_error!("the `subtle` feature requires the `alloc` feature");
+#[cfg(all(feature = "serde", not(feature = "alloc")))]
+compile_error!("the `serde` feature requires the `alloc` feature");
+#[cfg(all(feature = "serde_with", not(feature = "alloc")))]
+compile_error!("the `serde_with` feature requires the `alloc` feature");
+#[cfg(all(feature = "clap", not(feature = "alloc")))]
+compile_error!("the `clap` feature requires the `alloc` feature");
+#[cfg(all(feature = "rayon", not(feature = "alloc")))]
+compile_error!("the `rayon` feature requires the `alloc` feature");
+
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::fmt;
+#[cfg(all(feature = "zeroize", feature = "alloc"))]
+use zeroize::Zeroizing;
+
+pub use array::{const_encode, decode_array, encode_array, EncodedArray};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use batch::BatchEncoder;
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use boba_macros::bubblebabble;
+#[cfg(all(feature = "subtle", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "subtle", feature = "alloc"))))]
+pub use compare::{ct_eq_bytes, ct_eq_encoded};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use scan::{scan, Match};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use split::{split_concatenated, SplitError};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use stream::Encoder;
 
+mod array;
+#[cfg(feature = "alloc")]
+mod batch;
+#[cfg(any(feature = "heapless", feature = "arrayvec"))]
+mod bounded;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(all(feature = "clap", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "clap", feature = "alloc"))))]
+pub mod clap;
+#[cfg(all(feature = "subtle", feature = "alloc"))]
+mod compare;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod ct;
 mod decode;
 mod encode;
+#[cfg(all(feature = "rayon", feature = "alloc"))]
+mod par;
+#[cfg(feature = "pgp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pgp")))]
+pub mod pgp;
+#[cfg(feature = "alloc")]
+mod scan;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub mod serde;
+#[cfg(feature = "alloc")]
+mod split;
+#[cfg(feature = "ssh")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ssh")))]
+pub mod ssh;
+#[cfg(feature = "alloc")]
+mod stream;
+#[cfg(feature = "transcode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transcode")))]
+pub mod transcode;
+#[cfg(feature = "x509")]
+#[cfg_attr(docsrs, doc(cfg(feature = "x509")))]
+pub mod x509;
 
 /// Decoding errors from [`boba::decode`](decode()).
 ///
@@ -106,11 +408,13 @@ mod encode;
 ///   encoding alphabet.
 /// - The input does not start with a leading `x`.
 /// - The input does not end with a trailing `x`.
+/// - A tuple is not followed by a `-` separator.
 /// - The decoded result does not checksum properly.
 ///
 /// # Examples
 ///
 /// ```
+/// # #[cfg(feature = "alloc")] {
 /// # use boba::DecodeError;
 /// assert_eq!(boba::decode("x💎🦀x"), Err(DecodeError::InvalidByte(1)));
 /// assert_eq!(boba::decode("x789x"), Err(DecodeError::InvalidByte(1)));
@@ -119,12 +423,43 @@ mod encode;
 /// assert_eq!(boba::decode(""), Err(DecodeError::Corrupted));
 /// assert_eq!(boba::decode("z"), Err(DecodeError::Corrupted));
 /// assert_eq!(boba::decode("xx"), Err(DecodeError::Corrupted));
+/// assert_eq!(boba::decode("xaaaaxaexax"), Err(DecodeError::ChecksumMismatch));
+/// assert_eq!(boba::decode("xoamncxycx"), Err(DecodeError::Corrupted));
+/// # }
 /// ```
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DecodeError {
     /// Checksum mismatch when decoding input.
+    ///
+    /// This is also returned for a tuple whose separator is not `-` in an
+    /// input of valid length, since the tuples are misaligned and the checksum
+    /// cannot be validated. With an invalid length, such a tuple is reported
+    /// as [`Corrupted`](Self::Corrupted). Earlier releases stopped decoding at such a tuple
+    /// and could return a truncated result instead.
     ChecksumMismatch,
     /// Corrupted input caused a decoding failure.
+    ///
+    /// The decoders that write to fixed-capacity output, [`decode_array`],
+    /// [`decode_heapless`], [`decode_arrayvec`], and [`decode_into_buf`],
+    /// also return `Corrupted` when `encoded` is valid but its decoded bytes
+    /// do not fit in the output. Capacity is only checked once the whole
+    /// input has decoded successfully, so every other error, including
+    /// checksum and alphabet errors, means the input itself is invalid.
+    ///
+    /// ```
+    /// # use boba::DecodeError;
+    /// // Valid input, but `Pineapple` does not fit in 8 bytes.
+    /// let decoded = boba::decode_array::<_, 8>("xigak-nyryk-humil-bosek-sonax");
+    /// assert_eq!(decoded, Err(DecodeError::Corrupted));
+    /// // Invalid input is reported as such, whatever the capacity.
+    /// let decoded = boba::decode_array::<_, 8>("xigak-nyryk-Humil-bosek-sonax");
+    /// assert_eq!(decoded, Err(DecodeError::InvalidByte(12)));
+    /// ```
+    ///
+    /// [`decode_array`]: crate::decode_array
+    /// [`decode_heapless`]: https://docs.rs/boba/latest/boba/fn.decode_heapless.html
+    /// [`decode_arrayvec`]: https://docs.rs/boba/latest/boba/fn.decode_arrayvec.html
+    /// [`decode_into_buf`]: https://docs.rs/boba/latest/boba/fn.decode_into_buf.html
     Corrupted,
     /// Expected to process a consonant from the encoding alphabet, but got
     /// something else.
@@ -161,6 +496,82 @@ impl fmt::Display for DecodeError {
     }
 }
 
+/// Encoding errors from [`boba::encode_to_slice`](encode_to_slice()).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeError;
+/// let mut buf = [0; 4];
+/// assert_eq!(boba::encode_to_slice([], &mut buf), Err(EncodeError::BufferTooSmall));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EncodeError {
+    /// The output buffer is shorter than the [`encoded_len`] of the input.
+    BufferTooSmall,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => f.write_str("Output buffer is too small for encoding"),
+        }
+    }
+}
+
+/// Return the length of the Bubble Babble encoding of `len` bytes of data.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(boba::encoded_len(0), "xexax".len());
+/// assert_eq!(boba::encoded_len(9), "xigak-nyryk-humil-bosek-sonax".len());
+/// assert_eq!(boba::encoded_len(10), "xesef-disof-gytuf-katof-movif-baxux".len());
+/// ```
+///
+/// # Panics
+///
+/// If the encoded length overflows `usize`, this function panics.
+#[must_use]
+pub const fn encoded_len(len: usize) -> usize {
+    match encode::encoded_len(len) {
+        Some(len) => len,
+        None => panic!("encoded length overflows usize"),
+    }
+}
+
+/// Encode a byte slice with the Bubble Babble encoding into the front of
+/// `out`, returning the number of bytes written.
+///
+/// `out` must be at least [`encoded_len`] bytes long. Bytes after the encoding
+/// are left untouched. This function does not allocate, which allows encoding
+/// into buffers that are reused or that are larger than practical for a
+/// [`String`].
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeError;
+/// # fn example() -> Result<(), EncodeError> {
+/// let mut buf = [0; 64];
+/// let len = boba::encode_to_slice("Pineapple", &mut buf)?;
+/// assert_eq!(&buf[..len], b"xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `out` is too short to hold the encoding, [`EncodeError::BufferTooSmall`]
+/// is returned and `out` is not modified.
+pub fn encode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<usize, EncodeError> {
+    encode::inner_to_slice(data.as_ref(), out).ok_or(EncodeError::BufferTooSmall)
+}
+
 /// Encode a byte slice with the Bubble Babble encoding to a [`String`].
 ///
 /// # Examples
@@ -170,11 +581,40 @@ impl fmt::Display for DecodeError {
 /// assert_eq!(boba::encode("1234567890"), "xesef-disof-gytuf-katof-movif-baxux");
 /// assert_eq!(boba::encode("Pineapple"), "xigak-nyryk-humil-bosek-sonax");
 /// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 #[must_use]
 pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
     encode::inner(data.as_ref())
 }
 
+/// Encode a byte slice with the Bubble Babble encoding, appending the encoding
+/// to `encoded`.
+///
+/// Reusing one [`String`] across calls avoids allocating for every input. To
+/// encode many inputs into a single buffer with an index of where each
+/// encoding starts, use [`BatchEncoder`].
+///
+/// # Examples
+///
+/// ```
+/// let mut encoded = String::new();
+/// for data in &["Pineapple", "1234567890"] {
+///     encoded.clear();
+///     boba::encode_into(&mut encoded, data);
+///     assert_eq!(encoded, boba::encode(data));
+/// }
+///
+/// let mut line = String::from("fingerprint: ");
+/// boba::encode_into(&mut line, "Pineapple");
+/// assert_eq!(line, "fingerprint: xigak-nyryk-humil-bosek-sonax");
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn encode_into<T: AsRef<[u8]>>(encoded: &mut String, data: T) {
+    encode::inner_into(data.as_ref(), encoded);
+}
+
 /// Decode Bubble Babble-encoded byte slice to a [`Vec<u8>`](Vec).
 ///
 /// # Examples
@@ -211,18 +651,403 @@ pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
 /// assert_eq!(boba::decode("z"), Err(DecodeError::Corrupted));
 /// assert_eq!(boba::decode("xx"), Err(DecodeError::Corrupted));
 /// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn decode<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, DecodeError> {
     decode::inner(encoded.as_ref())
 }
 
-#[cfg(test)]
+/// Decode Bubble Babble-encoded byte slice, appending the decoded bytes to
+/// `decoded`.
+///
+/// Reusing one [`Vec<u8>`](Vec) across calls avoids allocating for every
+/// input.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let mut decoded = b"fruit: ".to_vec();
+/// boba::decode_into(&mut decoded, "xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(decoded, b"fruit: Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and might return [`DecodeError`] under the same
+/// conditions as [`decode`](decode()). On error, `decoded` is truncated to its
+/// original length.
+///
+/// ```
+/// # use boba::DecodeError;
+/// let mut decoded = b"fruit: ".to_vec();
+/// let result = boba::decode_into(&mut decoded, "xigak-nyryk-humil-bosek-sonxx");
+/// assert_eq!(result, Err(DecodeError::ExpectedVowel));
+/// assert_eq!(decoded, b"fruit: ");
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn decode_into<T: AsRef<[u8]>>(decoded: &mut Vec<u8>, encoded: T) -> Result<(), DecodeError> {
+    let len = decoded.len();
+    decode::inner_into(encoded.as_ref(), decoded).map_err(|err| {
+        decoded.truncate(len);
+        err
+    })
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a [`String`] that is
+/// zeroized when dropped.
+///
+/// The encoded output is allocated with its exact final size up front, so no
+/// copies of it are left behind by reallocation.
+///
+/// # Examples
+///
+/// ```
+/// let encoded = boba::encode_zeroizing("Pineapple");
+/// assert_eq!(*encoded, "xigak-nyryk-humil-bosek-sonax");
+/// ```
+#[cfg(all(feature = "zeroize", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "zeroize", feature = "alloc"))))]
+#[must_use]
+pub fn encode_zeroizing<T: AsRef<[u8]>>(data: T) -> Zeroizing<String> {
+    Zeroizing::new(encode::inner(data.as_ref()))
+}
+
+/// Decode Bubble Babble-encoded byte slice to a [`Vec<u8>`](Vec) that is
+/// zeroized when dropped.
+///
+/// The decoded output is allocated with enough capacity up front that it is
+/// never reallocated, so no copies of it are left behind. If decoding fails,
+/// any partially decoded output is zeroized before the error is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let decoded = boba::decode_zeroizing("xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(*decoded, b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and might return [`DecodeError`] under the same
+/// conditions as [`decode`](decode()).
+#[cfg(all(feature = "zeroize", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "zeroize", feature = "alloc"))))]
+pub fn decode_zeroizing<T: AsRef<[u8]>>(encoded: T) -> Result<Zeroizing<Vec<u8>>, DecodeError> {
+    let encoded = encoded.as_ref();
+    let mut decoded = Zeroizing::new(Vec::with_capacity(decode::decoded_capacity(encoded.len())));
+    decode::inner_into(encoded, &mut decoded)?;
+    Ok(decoded)
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a [`String`] using
+/// multiple threads.
+///
+/// The output is identical to [`encode`](encode()). Inputs are split into
+/// chunks that are encoded in parallel on the global [`rayon`] thread pool.
+/// The checksum at the start of each chunk is found with a prefix scan of the
+/// checksum updates of the preceding chunks. Small inputs, and all inputs
+/// when the current thread pool has a single thread, are encoded
+/// sequentially on the calling thread.
+///
+/// # Examples
+///
+/// ```
+/// let data = vec![0x5a; 1 << 20];
+/// assert_eq!(boba::par_encode(&data), boba::encode(&data));
+/// ```
+///
+/// [`rayon`]: https://docs.rs/rayon
+#[cfg(all(feature = "rayon", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "rayon", feature = "alloc"))))]
+#[must_use]
+pub fn par_encode<T: AsRef<[u8]>>(data: T) -> String {
+    par::encode(data.as_ref())
+}
+
+/// Decode Bubble Babble-encoded byte slice to a [`Vec<u8>`](Vec) using
+/// multiple threads.
+///
+/// The output is identical to [`decode`](decode()). Inputs are split into
+/// chunks that are validated and decoded in parallel on the global [`rayon`]
+/// thread pool. Small inputs, and all inputs when the current thread pool has
+/// a single thread, are decoded sequentially on the calling thread.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let encoded = boba::encode(vec![0x5a; 1 << 20]);
+/// assert_eq!(boba::par_decode(&encoded)?, boba::decode(&encoded)?);
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and returns the same [`DecodeError`] as
+/// [`decode`](decode()) for every invalid input.
+///
+/// [`rayon`]: https://docs.rs/rayon
+#[cfg(all(feature = "rayon", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "rayon", feature = "alloc"))))]
+pub fn par_decode<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, DecodeError> {
+    par::decode(encoded.as_ref())
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a fixed-capacity
+/// [`heapless::String`] without allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeError;
+/// # fn example() -> Result<(), EncodeError> {
+/// let encoded = boba::encode_heapless::<_, 32>("Pineapple")?;
+/// assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the [`encoded_len`] of `data` exceeds the capacity `N`,
+/// [`EncodeError::BufferTooSmall`] is returned.
+///
+/// [`heapless::String`]: https://docs.rs/heapless/0.8/heapless/struct.String.html
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub fn encode_heapless<T: AsRef<[u8]>, const N: usize>(
+    data: T,
+) -> Result<heapless::String<N>, EncodeError> {
+    bounded::encode_heapless(data.as_ref())
+}
+
+/// Decode Bubble Babble-encoded byte slice to a fixed-capacity
+/// [`heapless::Vec`] without allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let decoded = boba::decode_heapless::<_, 32>("xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(decoded, b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and might return [`DecodeError`] under the same
+/// conditions as [`decode`](decode()).
+///
+/// **If the decoded output does not fit in the capacity `N`,
+/// [`DecodeError::Corrupted`] is returned.** This is the same error returned
+/// for some malformed inputs. The capacity is only checked once the whole
+/// input has decoded successfully, so any other error means the input is
+/// invalid, but `Corrupted` alone does not say whether the input was invalid
+/// or too long. Choose `N` large enough for the longest expected input.
+///
+/// ```
+/// # use boba::DecodeError;
+/// // `Pineapple` is 9 bytes long.
+/// let decoded = boba::decode_heapless::<_, 8>("xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(decoded, Err(DecodeError::Corrupted));
+/// ```
+///
+/// [`heapless::Vec`]: https://docs.rs/heapless/0.8/heapless/struct.Vec.html
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub fn decode_heapless<T: AsRef<[u8]>, const N: usize>(
+    encoded: T,
+) -> Result<heapless::Vec<u8, N>, DecodeError> {
+    bounded::decode_heapless(encoded.as_ref())
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a fixed-capacity
+/// [`ArrayString`] without allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeError;
+/// # fn example() -> Result<(), EncodeError> {
+/// let encoded = boba::encode_arrayvec::<_, 32>("Pineapple")?;
+/// assert_eq!(encoded.as_str(), "xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the [`encoded_len`] of `data` exceeds the capacity `N`,
+/// [`EncodeError::BufferTooSmall`] is returned.
+///
+/// [`ArrayString`]: https://docs.rs/arrayvec/0.7/arrayvec/struct.ArrayString.html
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+pub fn encode_arrayvec<T: AsRef<[u8]>, const N: usize>(
+    data: T,
+) -> Result<arrayvec::ArrayString<N>, EncodeError> {
+    bounded::encode_arrayvec(data.as_ref())
+}
+
+/// Decode Bubble Babble-encoded byte slice to a fixed-capacity [`ArrayVec`]
+/// without allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let decoded = boba::decode_arrayvec::<_, 32>("xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(decoded.as_slice(), b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and might return [`DecodeError`] under the same
+/// conditions as [`decode`](decode()).
+///
+/// **If the decoded output does not fit in the capacity `N`,
+/// [`DecodeError::Corrupted`] is returned.** This is the same error returned
+/// for some malformed inputs. The capacity is only checked once the whole
+/// input has decoded successfully, so any other error means the input is
+/// invalid, but `Corrupted` alone does not say whether the input was invalid
+/// or too long. Choose `N` large enough for the longest expected input.
+///
+/// ```
+/// # use boba::DecodeError;
+/// // `Pineapple` is 9 bytes long.
+/// let decoded = boba::decode_arrayvec::<_, 8>("xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(decoded, Err(DecodeError::Corrupted));
+/// ```
+///
+/// [`ArrayVec`]: https://docs.rs/arrayvec/0.7/arrayvec/struct.ArrayVec.html
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+pub fn decode_arrayvec<T: AsRef<[u8]>, const N: usize>(
+    encoded: T,
+) -> Result<arrayvec::ArrayVec<u8, N>, DecodeError> {
+    bounded::decode_arrayvec(encoded.as_ref())
+}
+
+/// Encode the bytes remaining in a [`Buf`] with the Bubble Babble encoding
+/// to a [`String`].
+///
+/// The output is identical to [`encode`](encode()) of the remaining bytes.
+/// `data` is consumed chunk by chunk without first being copied into a
+/// contiguous buffer, so chained and other non-contiguous buffers are encoded
+/// directly, even when a pair of bytes is split across chunks.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::{Buf, Bytes};
+///
+/// let data = Bytes::from_static(b"Pine").chain(Bytes::from_static(b"apple"));
+/// assert_eq!(boba::encode_buf(data), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+///
+/// # Panics
+///
+/// If the encoded length overflows `usize`, this function panics.
+///
+/// [`Buf`]: https://docs.rs/bytes/1/bytes/trait.Buf.html
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "bytes", feature = "alloc"))))]
+#[must_use]
+pub fn encode_buf<B: bytes::Buf>(data: B) -> String {
+    buf::encode(data)
+}
+
+/// Decode Bubble Babble-encoded byte slice, writing the decoded bytes to a
+/// [`BufMut`].
+///
+/// # Examples
+///
+/// ```
+/// use bytes::BytesMut;
+///
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let mut decoded = BytesMut::new();
+/// boba::decode_into_buf(&mut decoded, "xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(decoded, &b"Pineapple"[..]);
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and might return [`DecodeError`] under the same
+/// conditions as [`decode`](decode()). The whole input is validated before
+/// anything is written, so on error `decoded` is left untouched.
+///
+/// **If `encoded` is valid but the [`remaining_mut`] capacity of `decoded` is
+/// too small for the decoded output, [`DecodeError::Corrupted`] is
+/// returned.** Errors in `encoded` take precedence. Growable buffers like
+/// [`BytesMut`] and [`Vec<u8>`] never run out of capacity.
+///
+/// ```
+/// # use boba::DecodeError;
+/// let mut buf = [0; 8];
+/// let mut out = &mut buf[..];
+/// let result = boba::decode_into_buf(&mut out, "xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(result, Err(DecodeError::Corrupted));
+///
+/// let mut buf = [0; 9];
+/// let mut out = &mut buf[..];
+/// boba::decode_into_buf(&mut out, "xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(&buf, b"Pineapple");
+/// # Ok::<(), DecodeError>(())
+/// ```
+///
+/// [`BufMut`]: https://docs.rs/bytes/1/bytes/trait.BufMut.html
+/// [`remaining_mut`]: https://docs.rs/bytes/1/bytes/trait.BufMut.html#tymethod.remaining_mut
+/// [`Vec<u8>`]: https://doc.rust-lang.org/stable/alloc/vec/struct.Vec.html
+/// [`BytesMut`]: https://docs.rs/bytes/1/bytes/struct.BytesMut.html
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub fn decode_into_buf<B: bytes::BufMut, T: AsRef<[u8]>>(
+    decoded: &mut B,
+    encoded: T,
+) -> Result<(), DecodeError> {
+    buf::decode_into(decoded, encoded.as_ref())
+}
+
+#[cfg(all(test, feature = "alloc"))]
 #[allow(clippy::non_ascii_literal)]
 mod tests {
     use alloc::string::String;
     use alloc::vec;
+    use alloc::vec::Vec;
     use core::fmt::Write as _;
 
-    use crate::{decode, encode, DecodeError};
+    use crate::{
+        decode, decode_into, encode, encode_into, encode_to_slice, encoded_len, DecodeError,
+        EncodeError,
+    };
 
     #[test]
     fn encoder() {
@@ -238,6 +1063,39 @@ mod tests {
         assert_eq!(encode("xyz!x6"), "xival-neved-cavuf-kexyx");
     }
 
+    #[test]
+    fn encode_to_slice_matches_encode() {
+        let mut data = vec![];
+        let mut buf = [0xFF; 1024];
+        for byte in 0..=255 {
+            let encoded = encode(&data);
+            assert_eq!(encoded_len(data.len()), encoded.len());
+            assert_eq!(encode_to_slice(&data, &mut buf), Ok(encoded.len()));
+            assert_eq!(&buf[..encoded.len()], encoded.as_bytes());
+            assert_eq!(
+                encode_to_slice(&data, &mut buf[..encoded.len() - 1]),
+                Err(EncodeError::BufferTooSmall)
+            );
+            data.push(byte);
+        }
+    }
+
+    #[test]
+    fn encode_into_and_decode_into_append() {
+        let mut encoded = String::from("prefix");
+        let mut decoded = b"prefix".to_vec();
+        // Cover inputs that span several of the encoder's internal blocks.
+        for len in (0..2048).step_by(97) {
+            let data = (0..len).map(|i: usize| (i % 251) as u8).collect::<Vec<_>>();
+            encoded.truncate(6);
+            encode_into(&mut encoded, &data);
+            assert_eq!(encoded[6..], encode(&data));
+            decoded.truncate(6);
+            assert_eq!(decode_into(&mut decoded, &encoded[6..]), Ok(()));
+            assert_eq!(decoded[6..], data[..]);
+        }
+    }
+
     #[test]
     fn decoder() {
         assert_eq!(decode("xexax"), Ok(vec![]));
@@ -266,6 +1124,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_error_sub_dash_does_not_truncate() {
+        assert_eq!(decode("xaaaaxaexax"), Err(DecodeError::ChecksumMismatch));
+        assert_eq!(decode("xoamncxycx"), Err(DecodeError::Corrupted));
+    }
+
     #[test]
     fn decode_sub_vowel_to_consonant() {
         assert_eq!(
@@ -318,6 +1182,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_error_bad_alphabet_after_structural_error() {
+        // Bytes outside of the alphabet are reported even when they appear
+        // after a tuple that fails to decode.
+        assert_eq!(
+            decode("xeeef-dizof-gytuf-katof-movif-baxuZ"),
+            Err(DecodeError::MalformedTrailer)
+        );
+        assert_eq!(
+            decode("xeeef-dizof-gytuf-katof-movif-baxZx"),
+            Err(DecodeError::InvalidByte(33))
+        );
+        assert_eq!(
+            decode("xigak-nyryk-humil-bosek-sonZx"),
+            Err(DecodeError::InvalidByte(27))
+        );
+        assert_eq!(
+            decode("xigax-nyryk-humil-bosek-so/ax"),
+            Err(DecodeError::InvalidByte(26))
+        );
+    }
+
+    #[test]
+    fn decode_never_reallocates() {
+        let mut data = vec![];
+        for byte in 0..=255 {
+            let encoded = encode(&data);
+            assert!(decode::decoded_capacity(encoded.len()) >= data.len());
+            data.push(byte);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroizing() {
+        use crate::{decode_zeroizing, encode_zeroizing};
+
+        assert_eq!(*encode_zeroizing("Pineapple"), encode("Pineapple"));
+        assert_eq!(
+            *decode_zeroizing("xigak-nyryk-humil-bosek-sonax").unwrap(),
+            b"Pineapple"
+        );
+        assert_eq!(
+            decode_zeroizing("xigak-nyryk-humil-bosek-sonxx"),
+            Err(DecodeError::ExpectedVowel)
+        );
+    }
+
     #[test]
     fn error_display_is_not_empty() {
         let test_cases = [
@@ -342,7 +1254,7 @@ mod tests {
 //
 // This module and macro declaration should be kept at the end of the file, in
 // order to not interfere with code coverage.
-#[cfg(doctest)]
+#[cfg(all(doctest, feature = "alloc"))]
 macro_rules! readme {
     ($x:expr) => {
         #[doc = $x]
@@ -352,5 +1264,5 @@ macro_rules! readme {
         readme!(include_str!("../README.md"));
     };
 }
-#[cfg(doctest)]
+#[cfg(all(doctest, feature = "alloc"))]
 readme!();
@@ -0,0 +1,503 @@
+//! Bubble Babble fingerprints of OpenPGP keys.
+//!
+//! OpenPGP fingerprints are usually displayed as 40 hexadecimal digits. This
+//! module parses OpenPGP public key packets and renders their fingerprints
+//! with Bubble Babble, which is easier to read aloud at key-signing parties.
+//!
+//! Keys may be given as binary packets or as an ASCII-armored
+//! `PGP PUBLIC KEY BLOCK`. Fingerprints are computed for:
+//!
+//! - Version 4 keys: SHA-1, as specified in [RFC 4880].
+//! - Version 5 keys: SHA-256, as specified in [`draft-ietf-openpgp-rfc4880bis`].
+//! - Version 6 keys: SHA-256, as specified in [RFC 9580], the OpenPGP
+//!   crypto-refresh.
+//!
+//! # Examples
+//!
+//! ```
+//! # use boba::pgp::{self, ParseError};
+//! # fn example() -> Result<(), ParseError> {
+//! # let armored = include_str!("../tests/fixtures/pgp/alice.asc");
+//! let fingerprint = pgp::fingerprint(armored)?;
+//! assert_eq!(fingerprint.version(), 4);
+//! assert_eq!(
+//!     fingerprint.to_string(),
+//!     "xebit-munym-rozut-kovyz-henyb-gokal-fetul-zedyp-pizih-tuhut-kyxux"
+//! );
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+//!
+//! [RFC 4880]: https://www.rfc-editor.org/rfc/rfc4880#section-12.2
+//! [`draft-ietf-openpgp-rfc4880bis`]: https://datatracker.ietf.org/doc/html/draft-ietf-openpgp-rfc4880bis-10#section-12.2
+//! [RFC 9580]: https://www.rfc-editor.org/rfc/rfc9580#section-5.5.4
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+const ARMOR_HEADER: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----";
+const ARMOR_TRAILER: &str = "-----END PGP PUBLIC KEY BLOCK-----";
+
+const TAG_PUBLIC_KEY: u8 = 6;
+const TAG_PUBLIC_SUBKEY: u8 = 14;
+
+/// Parsing errors from the fingerprint functions in [`boba::pgp`](self).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::pgp::{self, ParseError};
+/// assert_eq!(pgp::fingerprint([]), Err(ParseError::MissingPublicKey));
+/// assert_eq!(pgp::fingerprint([0x00]), Err(ParseError::MalformedPacket));
+/// assert_eq!(pgp::fingerprint([0xc6, 0x05, 0x04]), Err(ParseError::Truncated));
+/// assert_eq!(pgp::fingerprint([0xc6, 0x01, 0x03]), Err(ParseError::UnsupportedVersion(3)));
+/// assert_eq!(
+///     pgp::fingerprint("-----BEGIN PGP PUBLIC KEY BLOCK-----"),
+///     Err(ParseError::InvalidArmor)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseError {
+    /// The CRC-24 checksum of an ASCII-armored key did not match its contents.
+    ArmorChecksumMismatch,
+    /// The input was not a well-formed ASCII-armored public key block.
+    InvalidArmor,
+    /// A packet header was malformed or used an unsupported length encoding.
+    MalformedPacket,
+    /// The input did not contain a public key packet.
+    MissingPublicKey,
+    /// A packet extended past the end of the input.
+    Truncated,
+    /// A public key packet had an unsupported version.
+    UnsupportedVersion(u8),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ArmorChecksumMismatch => f.write_str("ASCII armor checksum mismatch"),
+            Self::InvalidArmor => f.write_str("Invalid ASCII armor"),
+            Self::MalformedPacket => f.write_str("Malformed packet"),
+            Self::MissingPublicKey => f.write_str("Missing public key packet"),
+            Self::Truncated => f.write_str("Packet extends past end of input"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported public key packet version {}", version)
+            }
+        }
+    }
+}
+
+/// An OpenPGP key fingerprint.
+///
+/// The [`Display`](fmt::Display) implementation writes the Bubble Babble
+/// encoding of the fingerprint.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fingerprint {
+    /// A 20 byte SHA-1 fingerprint of a version 4 key.
+    V4([u8; 20]),
+    /// A 32 byte SHA-256 fingerprint of a version 5 key.
+    V5([u8; 32]),
+    /// A 32 byte SHA-256 fingerprint of a version 6 key.
+    V6([u8; 32]),
+}
+
+impl Fingerprint {
+    /// The version of the key this fingerprint was computed from.
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        match self {
+            Self::V4(_) => 4,
+            Self::V5(_) => 5,
+            Self::V6(_) => 6,
+        }
+    }
+
+    /// The raw fingerprint digest.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::V4(digest) => digest,
+            Self::V5(digest) | Self::V6(digest) => digest,
+        }
+    }
+
+    /// Encode the fingerprint with [`boba::encode`](crate::encode()).
+    #[must_use]
+    pub fn encode(&self) -> String {
+        crate::encode(self.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for Fingerprint {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+/// Compute the fingerprint of the first public key packet in a binary or
+/// ASCII-armored OpenPGP key.
+///
+/// For a transferable public key, this is the fingerprint of the primary key.
+///
+/// # Errors
+///
+/// If the input is not well-formed or does not contain a supported public key
+/// packet, a [`ParseError`] is returned.
+pub fn fingerprint<T: AsRef<[u8]>>(key: T) -> Result<Fingerprint, ParseError> {
+    let packets = dearmor(key.as_ref())?;
+    for packet in Packets::new(&packets) {
+        let (tag, body) = packet?;
+        if tag == TAG_PUBLIC_KEY || tag == TAG_PUBLIC_SUBKEY {
+            return fingerprint_packet(body);
+        }
+    }
+    Err(ParseError::MissingPublicKey)
+}
+
+/// Compute the fingerprints of every public key and public subkey packet in a
+/// binary or ASCII-armored OpenPGP key, in the order they appear.
+///
+/// ```
+/// # use boba::pgp::{self, ParseError};
+/// # fn example() -> Result<(), ParseError> {
+/// # let binary = include_bytes!("../tests/fixtures/pgp/alice.gpg");
+/// let fingerprints = pgp::fingerprints(binary)?;
+/// assert_eq!(fingerprints.len(), 2);
+/// assert_eq!(
+///     fingerprints[1].to_string(),
+///     "xinev-pekes-bifeg-hahyr-zyhed-lacih-vonad-hynaz-samuz-vakat-fuxex"
+/// );
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the input is not well-formed or does not contain a supported public key
+/// packet, a [`ParseError`] is returned.
+pub fn fingerprints<T: AsRef<[u8]>>(key: T) -> Result<Vec<Fingerprint>, ParseError> {
+    let packets = dearmor(key.as_ref())?;
+    let mut fingerprints = Vec::new();
+    for packet in Packets::new(&packets) {
+        let (tag, body) = packet?;
+        if tag == TAG_PUBLIC_KEY || tag == TAG_PUBLIC_SUBKEY {
+            fingerprints.push(fingerprint_packet(body)?);
+        }
+    }
+    if fingerprints.is_empty() {
+        return Err(ParseError::MissingPublicKey);
+    }
+    Ok(fingerprints)
+}
+
+/// Compute the fingerprint of a public key packet body, starting with its
+/// version octet.
+fn fingerprint_packet(body: &[u8]) -> Result<Fingerprint, ParseError> {
+    match body.first() {
+        Some(4) => {
+            // A V4 fingerprint is the SHA-1 hash of the octet 0x99, followed
+            // by the two-octet packet length, followed by the entire packet
+            // body.
+            let len = u16::try_from(body.len()).map_err(|_| ParseError::MalformedPacket)?;
+            let mut hasher = Sha1::new();
+            hasher.update([0x99]);
+            hasher.update(len.to_be_bytes());
+            hasher.update(body);
+            Ok(Fingerprint::V4(hasher.finalize().into()))
+        }
+        Some(&version @ (5 | 6)) => {
+            // V5 and V6 fingerprints are the SHA-256 hash of the octet 0x9A
+            // or 0x9B respectively, followed by the four-octet packet length,
+            // followed by the entire packet body.
+            let len = u32::try_from(body.len()).map_err(|_| ParseError::MalformedPacket)?;
+            let mut hasher = Sha256::new();
+            hasher.update([0x95 + version]);
+            hasher.update(len.to_be_bytes());
+            hasher.update(body);
+            let digest = hasher.finalize().into();
+            if version == 5 {
+                Ok(Fingerprint::V5(digest))
+            } else {
+                Ok(Fingerprint::V6(digest))
+            }
+        }
+        Some(&version) => Err(ParseError::UnsupportedVersion(version)),
+        None => Err(ParseError::Truncated),
+    }
+}
+
+/// Iterator over `(tag, body)` pairs of the OpenPGP packets in a byte slice.
+struct Packets<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Packets<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn parse_next(&mut self) -> Result<(u8, &'a [u8]), ParseError> {
+        let (&ctb, rest) = self.data.split_first().ok_or(ParseError::Truncated)?;
+        if ctb & 0x80 == 0 {
+            return Err(ParseError::MalformedPacket);
+        }
+        let (tag, len, rest) = if ctb & 0x40 == 0 {
+            // Old format packet header.
+            let tag = (ctb >> 2) & 0x0f;
+            let (len, rest) = match ctb & 0x03 {
+                0 => take_be(rest, 1)?,
+                1 => take_be(rest, 2)?,
+                2 => take_be(rest, 4)?,
+                // Indeterminate length packets extend to the end of input.
+                _ => (rest.len(), rest),
+            };
+            (tag, len, rest)
+        } else {
+            // New format packet header.
+            let tag = ctb & 0x3f;
+            let (len, rest) = match rest {
+                [len @ 0..=191, rest @ ..] => (usize::from(*len), rest),
+                [hi @ 192..=223, lo, rest @ ..] => {
+                    ((usize::from(*hi - 192) << 8) + usize::from(*lo) + 192, rest)
+                }
+                [255, rest @ ..] => take_be(rest, 4)?,
+                // Partial body lengths are not permitted for key packets.
+                [224..=254, ..] => return Err(ParseError::MalformedPacket),
+                _ => return Err(ParseError::Truncated),
+            };
+            (tag, len, rest)
+        };
+        if len > rest.len() {
+            return Err(ParseError::Truncated);
+        }
+        let (body, rest) = rest.split_at(len);
+        self.data = rest;
+        Ok((tag, body))
+    }
+}
+
+impl<'a> Iterator for Packets<'a> {
+    type Item = Result<(u8, &'a [u8]), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let packet = self.parse_next();
+        if packet.is_err() {
+            // Stop iterating after the first malformed packet.
+            self.data = &[];
+        }
+        Some(packet)
+    }
+}
+
+/// Read a `width` octet big-endian length from the front of `data`.
+fn take_be(data: &[u8], width: usize) -> Result<(usize, &[u8]), ParseError> {
+    if data.len() < width {
+        return Err(ParseError::Truncated);
+    }
+    let (len, rest) = data.split_at(width);
+    let len = len
+        .iter()
+        .fold(0_u32, |acc, &byte| (acc << 8) | u32::from(byte));
+    let len = usize::try_from(len).map_err(|_| ParseError::MalformedPacket)?;
+    Ok((len, rest))
+}
+
+/// Strip ASCII armor from the input, if present, and return the binary
+/// packets.
+fn dearmor(data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let text = match core::str::from_utf8(data) {
+        Ok(text) if text.trim_start().starts_with(ARMOR_HEADER) => text,
+        _ => return Ok(data.to_vec()),
+    };
+    let mut lines = text
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.is_empty());
+    lines.next();
+    // Skip armor headers, e.g. `Comment: ...`, which end at a blank line.
+    for line in &mut lines {
+        if line.is_empty() {
+            break;
+        }
+        if !line.contains(": ") {
+            return Err(ParseError::InvalidArmor);
+        }
+    }
+    let mut body = String::new();
+    let mut checksum = None;
+    loop {
+        match lines.next() {
+            Some(ARMOR_TRAILER) => break,
+            Some(line) if line.starts_with('=') && checksum.is_none() => {
+                let crc = STANDARD
+                    .decode(&line[1..])
+                    .map_err(|_| ParseError::InvalidArmor)?;
+                checksum = Some(crc);
+            }
+            Some(line) if checksum.is_none() => body.push_str(line),
+            _ => return Err(ParseError::InvalidArmor),
+        }
+    }
+    let packets = STANDARD
+        .decode(body)
+        .map_err(|_| ParseError::InvalidArmor)?;
+    if let Some(checksum) = checksum {
+        let crc = crc24(&packets).to_be_bytes();
+        if checksum != crc[1..] {
+            return Err(ParseError::ArmorChecksumMismatch);
+        }
+    }
+    Ok(packets)
+}
+
+/// CRC-24 checksum of ASCII armor as specified in RFC 4880, Section 6.1.
+fn crc24(data: &[u8]) -> u32 {
+    const CRC24_INIT: u32 = 0x00B7_04CE;
+    const CRC24_POLY: u32 = 0x0186_4CFB;
+
+    let mut crc = CRC24_INIT;
+    for &byte in data {
+        crc ^= u32::from(byte) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0x00FF_FFFF
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::{fingerprint, fingerprints, Fingerprint, ParseError};
+
+    const ARMORED: &str = include_str!("../tests/fixtures/pgp/alice.asc");
+    const BINARY: &[u8] = include_bytes!("../tests/fixtures/pgp/alice.gpg");
+
+    // Captured with:
+    //
+    // ```sh
+    // gpg --with-colons --fingerprint --with-subkey-fingerprints alice
+    // ```
+    const PRIMARY: [u8; 20] = [
+        0x02, 0xD8, 0xE6, 0x8B, 0x7F, 0xD6, 0x39, 0xF5, 0xA6, 0x04, 0x1B, 0x73, 0x36, 0x7F, 0x48,
+        0xAA, 0x3F, 0x5D, 0x17, 0xD6,
+    ];
+    const SUBKEY: [u8; 20] = [
+        0x65, 0xEA, 0xD8, 0xC0, 0x0F, 0x45, 0x97, 0xBF, 0xD6, 0x27, 0x46, 0x5E, 0x25, 0x25, 0x26,
+        0xFC, 0xE2, 0xFE, 0xD8, 0xD3,
+    ];
+
+    // Primary key packet of the sample v6 certificate in RFC 9580,
+    // Appendix A.3.
+    const V6_PACKET: [u8; 44] = [
+        0xc6, 0x2a, 0x06, 0x63, 0x87, 0x7f, 0xe3, 0x1b, 0x00, 0x00, 0x00, 0x20, 0xf9, 0x4d, 0xa7,
+        0xbb, 0x48, 0xd6, 0x0a, 0x61, 0xe5, 0x67, 0x70, 0x6a, 0x65, 0x87, 0xd0, 0x33, 0x19, 0x99,
+        0xbb, 0x9d, 0x89, 0x1a, 0x08, 0x24, 0x2e, 0xad, 0x84, 0x54, 0x3d, 0xf8, 0x95, 0xa3,
+    ];
+    const V6_FINGERPRINT: [u8; 32] = [
+        0xcb, 0x18, 0x6c, 0x4f, 0x06, 0x09, 0xa6, 0x97, 0xe4, 0xd5, 0x2d, 0xfa, 0x6c, 0x72, 0x2b,
+        0x0c, 0x1f, 0x1e, 0x27, 0xc1, 0x8a, 0x56, 0x70, 0x8f, 0x65, 0x25, 0xec, 0x27, 0xba, 0xd9,
+        0xac, 0xc9,
+    ];
+
+    // The same key material as `V6_PACKET` framed as a v5 key, with an old
+    // format packet header. The expected fingerprint was computed with:
+    //
+    // ```python
+    // hashlib.sha256(b"\x9a" + len(body).to_bytes(4, "big") + body).hexdigest()
+    // ```
+    const V5_PACKET: [u8; 44] = [
+        0x98, 0x2a, 0x05, 0x63, 0x87, 0x7f, 0xe3, 0x1b, 0x00, 0x00, 0x00, 0x20, 0xf9, 0x4d, 0xa7,
+        0xbb, 0x48, 0xd6, 0x0a, 0x61, 0xe5, 0x67, 0x70, 0x6a, 0x65, 0x87, 0xd0, 0x33, 0x19, 0x99,
+        0xbb, 0x9d, 0x89, 0x1a, 0x08, 0x24, 0x2e, 0xad, 0x84, 0x54, 0x3d, 0xf8, 0x95, 0xa3,
+    ];
+    const V5_FINGERPRINT: [u8; 32] = [
+        0xc8, 0x26, 0xe5, 0x65, 0x67, 0x90, 0x5f, 0x80, 0x6f, 0x41, 0xd7, 0x43, 0xec, 0xb8, 0xbe,
+        0xa5, 0xd1, 0x31, 0x32, 0xd9, 0xad, 0xe6, 0xdd, 0xee, 0x79, 0x29, 0xf2, 0x95, 0x54, 0x34,
+        0x6c, 0x76,
+    ];
+
+    #[test]
+    fn v4_armored_and_binary() {
+        let expected = [Fingerprint::V4(PRIMARY), Fingerprint::V4(SUBKEY)];
+        assert_eq!(fingerprints(ARMORED).unwrap(), expected);
+        assert_eq!(fingerprints(BINARY).unwrap(), expected);
+        assert_eq!(fingerprint(ARMORED).unwrap(), expected[0]);
+    }
+
+    #[test]
+    fn v5() {
+        assert_eq!(
+            fingerprint(V5_PACKET).unwrap(),
+            Fingerprint::V5(V5_FINGERPRINT)
+        );
+    }
+
+    #[test]
+    fn v6() {
+        assert_eq!(
+            fingerprint(V6_PACKET).unwrap(),
+            Fingerprint::V6(V6_FINGERPRINT)
+        );
+    }
+
+    #[test]
+    fn armor_checksum_mismatch() {
+        let crc_line = ARMORED.lines().find(|line| line.starts_with('=')).unwrap();
+        let corrupted = ARMORED.replace(crc_line, "=AAAA");
+        assert_eq!(
+            fingerprint(corrupted),
+            Err(ParseError::ArmorChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn armor_without_checksum() {
+        let stripped = ARMORED.lines().filter(|line| !line.starts_with('=')).fold(
+            String::new(),
+            |mut acc, line| {
+                acc.push_str(line);
+                acc.push('\n');
+                acc
+            },
+        );
+        assert_eq!(fingerprint(stripped), Ok(Fingerprint::V4(PRIMARY)));
+    }
+
+    #[test]
+    fn truncated_binary() {
+        assert_eq!(
+            fingerprint(&BINARY[..BINARY.len() / 2]),
+            Ok(Fingerprint::V4(PRIMARY))
+        );
+        assert_eq!(
+            fingerprints(&BINARY[..BINARY.len() / 2]),
+            Err(ParseError::Truncated)
+        );
+    }
+}
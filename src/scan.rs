@@ -0,0 +1,172 @@
+//! Find Bubble Babble encodings in arbitrary text.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A Bubble Babble encoding found in text by [`scan`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Match<'a> {
+    start: usize,
+    encoded: &'a [u8],
+    decoded: Vec<u8>,
+}
+
+impl<'a> Match<'a> {
+    /// The byte offset of the start of the encoding in the scanned text.
+    #[must_use]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset of the end of the encoding in the scanned text.
+    #[must_use]
+    pub fn end(&self) -> usize {
+        self.start + self.encoded.len()
+    }
+
+    /// The byte range of the encoding in the scanned text.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.start()..self.end()
+    }
+
+    /// The encoding as it appears in the scanned text.
+    #[must_use]
+    pub fn encoded(&self) -> &'a [u8] {
+        self.encoded
+    }
+
+    /// The decoded bytes.
+    #[must_use]
+    pub fn decoded(&self) -> &[u8] {
+        &self.decoded
+    }
+
+    /// Consume the match and return the decoded bytes.
+    #[must_use]
+    pub fn into_decoded(self) -> Vec<u8> {
+        self.decoded
+    }
+}
+
+/// Find Bubble Babble encodings in `text`.
+///
+/// `text` is split into words of ASCII alphanumeric characters, `_`, and `-`.
+/// Leading and trailing `-` are trimmed from each word. Words that decode with
+/// [`boba::decode`](crate::decode()), including checksum validation, are
+/// yielded in the order they appear.
+///
+/// An encoding must not be directly adjacent to other word characters. For
+/// example, `xexax` is found in `id=xexax.` but not in `boxexaxes`.
+///
+/// # Examples
+///
+/// ```
+/// let log = "2024-01-01 key xigak-nyryk-humil-bosek-sonax accepted, xexax-xexax rejected";
+/// let matches = boba::scan(log).collect::<Vec<_>>();
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].range(), 15..44);
+/// assert_eq!(matches[0].encoded(), b"xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(matches[0].decoded(), b"Pineapple");
+/// ```
+pub fn scan<T>(text: &T) -> impl Iterator<Item = Match<'_>>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    let candidates = Candidates {
+        text: text.as_ref(),
+        pos: 0,
+    };
+    candidates.filter_map(|(start, encoded)| {
+        let decoded = crate::decode(encoded).ok()?;
+        Some(Match {
+            start,
+            encoded,
+            decoded,
+        })
+    })
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+}
+
+/// Iterates over words in the text that have the shape of a Bubble Babble
+/// encoding: an `x` header and trailer and a length of `6k + 5` bytes.
+#[derive(Debug, Clone)]
+struct Candidates<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for Candidates<'a> {
+    type Item = (usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.text[self.pos..];
+            let start = self.pos + rest.iter().position(|&byte| is_word_byte(byte))?;
+            let rest = &self.text[start..];
+            let len = rest
+                .iter()
+                .position(|&byte| !is_word_byte(byte))
+                .unwrap_or(rest.len());
+            self.pos = start + len;
+
+            let word = &rest[..len];
+            let leading = word.iter().take_while(|&&byte| byte == b'-').count();
+            let word = &word[leading..];
+            let trailing = word.iter().rev().take_while(|&&byte| byte == b'-').count();
+            let word = &word[..word.len() - trailing];
+            if word.len() % 6 == 5 && word.starts_with(b"x") && word.ends_with(b"x") {
+                return Some((start + leading, word));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::scan;
+
+    fn spans(text: &str) -> Vec<(usize, usize)> {
+        scan(text).map(|m| (m.start(), m.end())).collect()
+    }
+
+    #[test]
+    fn word_boundaries() {
+        assert_eq!(spans("xexax"), [(0, 5)]);
+        assert_eq!(spans("(xexax)"), [(1, 6)]);
+        assert_eq!(spans("--xexax--"), [(2, 7)]);
+        assert_eq!(spans("boxexaxes"), []);
+        assert_eq!(spans("xexax_id"), []);
+        assert_eq!(spans("1xexax"), []);
+    }
+
+    #[test]
+    fn multiple_matches() {
+        let text = "a=xexax\nb=xesef-disof-gytuf-katof-movif-baxux\n";
+        let matches = scan(text).collect::<Vec<_>>();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].range(), 2..7);
+        assert_eq!(matches[0].decoded(), b"");
+        assert_eq!(matches[1].range(), 10..45);
+        assert_eq!(matches[1].clone().into_decoded(), b"1234567890");
+    }
+
+    #[test]
+    fn skips_invalid_checksum() {
+        assert_eq!(spans("xesef-disof-gytuf-katof-movif-baxox"), []);
+        assert_eq!(spans("xigak-nyryk-Humil-bosek-sonax"), []);
+    }
+
+    #[test]
+    fn non_utf8_text() {
+        let text = b"\xFF\xFExexax\xFF";
+        let matches = scan(&text[..]).collect::<Vec<_>>();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].encoded(), b"xexax");
+    }
+}
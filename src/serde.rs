@@ -0,0 +1,275 @@
+//! Serialize and deserialize byte fields as Bubble Babble with [`serde`].
+//!
+//! In human-readable formats like JSON, TOML, and YAML, bytes are serialized
+//! as Bubble Babble strings. In binary formats, bytes are serialized as raw
+//! bytes.
+//!
+//! Use this module with the `#[serde(with = "boba::serde")]` field attribute.
+//! Fields may be any type that implements `AsRef<[u8]>` and
+//! `TryFrom<Vec<u8>>`, such as [`Vec<u8>`](Vec) and `[u8; N]`. For
+//! [`Option`] fields, use `#[serde(with = "boba::serde::option")]`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Key {
+//!     #[serde(with = "boba::serde")]
+//!     fingerprint: [u8; 9],
+//!     #[serde(with = "boba::serde::option")]
+//!     comment: Option<Vec<u8>>,
+//! }
+//!
+//! let key = Key {
+//!     fingerprint: *b"Pineapple",
+//!     comment: None,
+//! };
+//! let json = serde_json::to_string(&key).unwrap();
+//! assert_eq!(json, r#"{"fingerprint":"xigak-nyryk-humil-bosek-sonax","comment":null}"#);
+//! assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), key);
+//! ```
+//!
+//! With the `serde_with` feature, the [`BubbleBabble`] adapter can be used
+//! with [`serde_with::serde_as`] to encode bytes nested in other types.
+//!
+//! [`serde_with::serde_as`]: https://docs.rs/serde_with/latest/serde_with/attr.serde_as.html
+#![cfg_attr(
+    not(feature = "serde_with"),
+    doc = "[`BubbleBabble`]: https://docs.rs/boba/latest/boba/serde/struct.BubbleBabble.html"
+)]
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use ::serde::de::{self, Deserializer, SeqAccess, Visitor};
+use ::serde::Serializer;
+
+use crate::DecodeError;
+
+/// Serialize bytes as a Bubble Babble string in human-readable formats and as
+/// raw bytes otherwise.
+///
+/// # Errors
+///
+/// If the serializer fails, its error is returned.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + AsRef<[u8]>,
+    S: Serializer,
+{
+    let bytes = bytes.as_ref();
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&crate::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserialize bytes from a Bubble Babble string in human-readable formats and
+/// from raw bytes otherwise.
+///
+/// # Errors
+///
+/// If the Bubble Babble string fails to decode, the [`DecodeError`] is mapped
+/// into a deserializer error whose message includes the position of any
+/// invalid byte. If the decoded bytes do not fit in `T`, for example when
+/// deserializing into a fixed-size array, an invalid length error is returned.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    let bytes = if deserializer.is_human_readable() {
+        deserializer.deserialize_str(EncodedVisitor)?
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)?
+    };
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| de::Error::invalid_length(len, &"bytes of the expected length"))
+}
+
+/// Serialize and deserialize [`Option`] byte fields as Bubble Babble.
+///
+/// Use this module with the `#[serde(with = "boba::serde::option")]` field
+/// attribute.
+pub mod option {
+    use alloc::vec::Vec;
+    use core::convert::TryFrom;
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use ::serde::de::{Deserializer, Visitor};
+    use ::serde::Serializer;
+
+    /// Serialize `Some` bytes with [`boba::serde::serialize`](super::serialize)
+    /// and `None` as a unit.
+    ///
+    /// # Errors
+    ///
+    /// If the serializer fails, its error is returned.
+    pub fn serialize<T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        match bytes {
+            Some(bytes) => serializer.serialize_some(&Encoded(bytes.as_ref())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize optional bytes with
+    /// [`boba::serde::deserialize`](super::deserialize).
+    ///
+    /// # Errors
+    ///
+    /// See [`boba::serde::deserialize`](super::deserialize).
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(OptionVisitor(PhantomData))
+    }
+
+    struct Encoded<'a>(&'a [u8]);
+
+    impl ::serde::Serialize for Encoded<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(self.0, serializer)
+        }
+    }
+
+    struct OptionVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for OptionVisitor<T>
+    where
+        T: TryFrom<Vec<u8>>,
+    {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("optional Bubble Babble-encoded bytes")
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            super::deserialize(deserializer).map(Some)
+        }
+    }
+}
+
+/// An adapter for [`serde_with`] that serializes bytes as Bubble Babble.
+///
+/// `BubbleBabble` implements [`SerializeAs`](serde_with::SerializeAs) and
+/// [`DeserializeAs`](serde_with::DeserializeAs) with the same behavior as
+/// [`boba::serde::serialize`](serialize) and
+/// [`boba::serde::deserialize`](deserialize).
+///
+/// # Examples
+///
+/// ```
+/// # use serde_derive::{Deserialize, Serialize};
+/// use boba::serde::BubbleBabble;
+/// use serde_with::serde_as;
+///
+/// #[serde_as]
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Keyring {
+///     #[serde_as(as = "Vec<BubbleBabble>")]
+///     fingerprints: Vec<Vec<u8>>,
+/// }
+///
+/// let keyring = Keyring {
+///     fingerprints: vec![b"Pineapple".to_vec(), vec![]],
+/// };
+/// let json = serde_json::to_string(&keyring).unwrap();
+/// assert_eq!(json, r#"{"fingerprints":["xigak-nyryk-humil-bosek-sonax","xexax"]}"#);
+/// assert_eq!(serde_json::from_str::<Keyring>(&json).unwrap(), keyring);
+/// ```
+#[cfg(feature = "serde_with")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BubbleBabble;
+
+#[cfg(feature = "serde_with")]
+impl<T> serde_with::SerializeAs<T> for BubbleBabble
+where
+    T: AsRef<[u8]>,
+{
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "serde_with")]
+impl<'de, T> serde_with::DeserializeAs<'de, T> for BubbleBabble
+where
+    T: TryFrom<Vec<u8>>,
+{
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+/// Decodes Bubble Babble strings in human-readable formats.
+struct EncodedVisitor;
+
+impl Visitor<'_> for EncodedVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a Bubble Babble-encoded string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        crate::decode(v).map_err(decode_error)
+    }
+}
+
+/// Collects raw bytes in binary formats.
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+fn decode_error<E: de::Error>(err: DecodeError) -> E {
+    E::custom(format_args!("invalid Bubble Babble encoding: {}", err))
+}
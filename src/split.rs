@@ -0,0 +1,166 @@
+//! Split encodings that were concatenated without delimiters.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::DecodeError;
+
+/// Error returned by [`split_concatenated`] when a segment fails to decode.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// let err = boba::split_concatenated("xexaxxigak-nyryk-Humil-bosek-sonax").unwrap_err();
+/// assert_eq!(err.segment(), 5..34);
+/// // Positions of invalid bytes are offsets into the concatenated input.
+/// assert_eq!(err.decode_error(), DecodeError::InvalidByte(17));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SplitError {
+    start: usize,
+    end: usize,
+    err: DecodeError,
+}
+
+impl SplitError {
+    /// The byte range of the segment that failed to decode.
+    #[must_use]
+    pub fn segment(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// The error from decoding the segment.
+    ///
+    /// The position in a [`DecodeError::InvalidByte`] is an offset into the
+    /// concatenated input, not into the segment.
+    #[must_use]
+    pub fn decode_error(&self) -> DecodeError {
+        self.err
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SplitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.err)
+    }
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid encoding at bytes {}..{}: {}",
+            self.start, self.end, self.err
+        )
+    }
+}
+
+/// Decode a sequence of encodings that were concatenated back to back, such as
+/// `xexaxxigak-nyryk-humil-bosek-sonax`.
+///
+/// Every encoding begins and ends with `x`. Within an encoding, `x` only
+/// appears as the checksum marker in the middle of the final partial tuple,
+/// where it is surrounded by vowels. Two adjacent `x` bytes therefore always
+/// mark the trailer of one encoding and the header of the next, so the
+/// boundaries are unambiguous and each segment is decoded with
+/// [`boba::decode`](crate::decode()), including checksum validation.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::SplitError;
+/// # fn example() -> Result<(), SplitError> {
+/// let segments = boba::split_concatenated("xexaxxigak-nyryk-humil-bosek-sonaxxexax")?;
+/// assert_eq!(segments, [&b""[..], b"Pineapple", b""]);
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If any segment fails to decode, a [`SplitError`] is returned with the span
+/// of the segment and its [`DecodeError`]. Empty input is a single empty
+/// segment, which is not a valid encoding.
+pub fn split_concatenated<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<Vec<u8>>, SplitError> {
+    let encoded = encoded.as_ref();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = encoded[start..]
+            .windows(2)
+            .position(|window| window == b"xx")
+            .map_or(encoded.len(), |idx| start + idx + 1);
+        let decoded = crate::decode(&encoded[start..end]).map_err(|err| {
+            let err = match err {
+                DecodeError::InvalidByte(pos) => DecodeError::InvalidByte(start + pos),
+                err => err,
+            };
+            SplitError { start, end, err }
+        })?;
+        segments.push(decoded);
+        if end == encoded.len() {
+            return Ok(segments);
+        }
+        start = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_concatenated;
+    use crate::{encode, DecodeError};
+
+    #[test]
+    fn round_trip() {
+        let inputs: [&[u8]; 5] = [b"", b"1", b"12", b"Pineapple", b"1234567890"];
+        for &first in &inputs {
+            for &second in &inputs {
+                for &third in &inputs {
+                    let mut concatenated = encode(first);
+                    concatenated.push_str(&encode(second));
+                    concatenated.push_str(&encode(third));
+                    assert_eq!(
+                        split_concatenated(&concatenated).unwrap(),
+                        [first, second, third],
+                        "{}",
+                        concatenated
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn single_encoding() {
+        assert_eq!(
+            split_concatenated("xigak-nyryk-humil-bosek-sonax").unwrap(),
+            [b"Pineapple".to_vec()]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let err = split_concatenated("").unwrap_err();
+        assert_eq!(err.segment(), 0..0);
+        assert_eq!(err.decode_error(), DecodeError::Corrupted);
+    }
+
+    #[test]
+    fn checksum_mismatch_in_segment() {
+        let err = split_concatenated("xexaxxesef-disof-gytuf-katof-movif-baxox").unwrap_err();
+        assert_eq!(err.segment(), 5..40);
+        assert_eq!(err.decode_error(), DecodeError::ChecksumMismatch);
+    }
+
+    #[test]
+    fn triple_x() {
+        // `xxx` cannot be split into valid encodings.
+        let err = split_concatenated("xexaxxxexax").unwrap_err();
+        assert_eq!(err.segment(), 5..6);
+        assert_eq!(err.decode_error(), DecodeError::MalformedTrailer);
+    }
+}
@@ -0,0 +1,333 @@
+//! Bubble Babble fingerprints of OpenSSH public keys.
+//!
+//! `ssh-keygen -B` prints the Bubble Babble encoding of the SHA-1 digest of a
+//! public key blob. This module parses the line formats that hold OpenSSH
+//! public keys and computes the same fingerprint:
+//!
+//! - `.pub` files written by `ssh-keygen`, e.g.
+//!   `ssh-ed25519 AAAA... alice@example.com`.
+//! - `authorized_keys` entries, which may be prefixed with a comma-separated
+//!   list of options.
+//! - `known_hosts` entries, which are prefixed with a list of host names and
+//!   an optional `@cert-authority` or `@revoked` marker.
+//!
+//! # Examples
+//!
+//! ```
+//! # use boba::ssh::{ParseError, PublicKey};
+//! # fn example() -> Result<(), ParseError> {
+//! let line = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAICLh+EIRVGe3pqElKij0MnvlZtxv7uwi3dFKTcrK7WJV alice@example.com";
+//! let key = PublicKey::parse(line)?;
+//! assert_eq!(key.key_type(), "ssh-ed25519");
+//! assert_eq!(key.comment(), Some("alice@example.com"));
+//! assert_eq!(
+//!     key.fingerprint(),
+//!     "xukek-nalig-gesim-vykyd-dapom-benuh-daniv-hadur-dunak-hidan-poxix"
+//! );
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use sha1::{Digest, Sha1};
+
+/// Parsing errors from the line parsers on [`PublicKey`].
+///
+/// # Examples
+///
+/// ```
+/// # use boba::ssh::{ParseError, PublicKey};
+/// assert_eq!(PublicKey::parse(""), Err(ParseError::Empty));
+/// assert_eq!(PublicKey::parse("# comment"), Err(ParseError::Empty));
+/// assert_eq!(PublicKey::parse("ssh-ed25519"), Err(ParseError::MissingKeyBlob));
+/// assert_eq!(PublicKey::parse("ssh-ed25519 !!!!"), Err(ParseError::InvalidBase64));
+/// assert_eq!(PublicKey::parse("ssh-ed25519 AAAA"), Err(ParseError::MalformedKeyBlob));
+/// assert_eq!(
+///     PublicKey::parse("ssh-rsa AAAAC3NzaC1lZDI1NTE5AAAAICLh+EIRVGe3pqElKij0MnvlZtxv7uwi3dFKTcrK7WJV"),
+///     Err(ParseError::KeyTypeMismatch)
+/// );
+/// assert_eq!(PublicKey::parse_known_hosts("@revoked"), Err(ParseError::MissingKeyType));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseError {
+    /// The line is blank or a `#` comment.
+    Empty,
+    /// The key blob was not valid base64.
+    InvalidBase64,
+    /// The key type named in the line does not match the key type embedded
+    /// in the key blob.
+    KeyTypeMismatch,
+    /// The key blob is too short to contain its embedded key type.
+    MalformedKeyBlob,
+    /// The line ended before the base64-encoded key blob.
+    MissingKeyBlob,
+    /// The line ended before the key type.
+    MissingKeyType,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("Line is empty or a comment"),
+            Self::InvalidBase64 => f.write_str("Key blob is not valid base64"),
+            Self::KeyTypeMismatch => f.write_str("Key type does not match key blob"),
+            Self::MalformedKeyBlob => f.write_str("Key blob is malformed"),
+            Self::MissingKeyBlob => f.write_str("Missing key blob"),
+            Self::MissingKeyType => f.write_str("Missing key type"),
+        }
+    }
+}
+
+/// An OpenSSH public key parsed from a line of text.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PublicKey {
+    key_type: String,
+    blob: Vec<u8>,
+    comment: Option<String>,
+}
+
+impl PublicKey {
+    /// Parse a public key in the format written to `.pub` files by
+    /// `ssh-keygen`: a key type, a base64-encoded key blob, and an optional
+    /// comment.
+    ///
+    /// # Errors
+    ///
+    /// If the line does not contain a key type followed by a valid key blob,
+    /// a [`ParseError`] is returned.
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Err(ParseError::Empty);
+        }
+        let (key_type, rest) = split_field(line);
+        let (encoded, rest) = split_field(rest);
+        if encoded.is_empty() {
+            return Err(ParseError::MissingKeyBlob);
+        }
+        let blob = STANDARD
+            .decode(encoded)
+            .map_err(|_| ParseError::InvalidBase64)?;
+        if embedded_key_type(&blob)? != key_type.as_bytes() {
+            return Err(ParseError::KeyTypeMismatch);
+        }
+        let comment = Some(rest).filter(|comment| !comment.is_empty());
+        Ok(Self {
+            key_type: key_type.into(),
+            blob,
+            comment: comment.map(String::from),
+        })
+    }
+
+    /// Parse an entry from an `authorized_keys` file.
+    ///
+    /// Entries may begin with a comma-separated list of options, which are
+    /// skipped. Option values may be double-quoted and contain spaces.
+    ///
+    /// ```
+    /// # use boba::ssh::{ParseError, PublicKey};
+    /// # fn example() -> Result<(), ParseError> {
+    /// let line = r#"command="echo hello world",no-pty ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAICLh+EIRVGe3pqElKij0MnvlZtxv7uwi3dFKTcrK7WJV alice@example.com"#;
+    /// let key = PublicKey::parse_authorized_keys(line)?;
+    /// assert_eq!(
+    ///     key.fingerprint(),
+    ///     "xukek-nalig-gesim-vykyd-dapom-benuh-daniv-hadur-dunak-hidan-poxix"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the line does not contain a key type followed by a valid key blob,
+    /// a [`ParseError`] is returned.
+    pub fn parse_authorized_keys(line: &str) -> Result<Self, ParseError> {
+        // Like `sshd`, first attempt to parse the line as a bare key and fall
+        // back to skipping an options field.
+        match Self::parse(line) {
+            Err(ParseError::Empty) => Err(ParseError::Empty),
+            Err(err) => {
+                let (_, rest) = split_options(line.trim());
+                if rest.is_empty() {
+                    return Err(err);
+                }
+                Self::parse(rest).map_err(|_| err)
+            }
+            Ok(key) => Ok(key),
+        }
+    }
+
+    /// Parse an entry from a `known_hosts` file.
+    ///
+    /// Entries begin with an optional `@cert-authority` or `@revoked` marker
+    /// and a comma-separated list of host name patterns, which may be hashed.
+    /// Both are skipped.
+    ///
+    /// ```
+    /// # use boba::ssh::{ParseError, PublicKey};
+    /// # fn example() -> Result<(), ParseError> {
+    /// let line = "example.com,192.0.2.1 ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAICLh+EIRVGe3pqElKij0MnvlZtxv7uwi3dFKTcrK7WJV";
+    /// let key = PublicKey::parse_known_hosts(line)?;
+    /// assert_eq!(
+    ///     key.fingerprint(),
+    ///     "xukek-nalig-gesim-vykyd-dapom-benuh-daniv-hadur-dunak-hidan-poxix"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the line does not contain a key type followed by a valid key blob,
+    /// a [`ParseError`] is returned.
+    pub fn parse_known_hosts(line: &str) -> Result<Self, ParseError> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Err(ParseError::Empty);
+        }
+        let rest = if line.starts_with('@') {
+            split_field(line).1
+        } else {
+            line
+        };
+        let (_hostnames, rest) = split_field(rest);
+        if rest.is_empty() {
+            return Err(ParseError::MissingKeyType);
+        }
+        Self::parse(rest)
+    }
+
+    /// The key type of this public key, e.g. `ssh-ed25519`.
+    #[must_use]
+    pub fn key_type(&self) -> &str {
+        &self.key_type
+    }
+
+    /// The decoded key blob of this public key.
+    #[must_use]
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+
+    /// The comment trailing this public key, if any.
+    #[must_use]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// The Bubble Babble fingerprint of this public key, as printed by
+    /// `ssh-keygen -B`.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        fingerprint(&self.blob)
+    }
+}
+
+/// Compute the Bubble Babble fingerprint of a raw public key blob, as printed
+/// by `ssh-keygen -B`.
+///
+/// The fingerprint is the Bubble Babble encoding of the SHA-1 digest of the
+/// blob.
+#[must_use]
+pub fn fingerprint<T: AsRef<[u8]>>(blob: T) -> String {
+    crate::encode(Sha1::digest(blob.as_ref()))
+}
+
+/// Read the key type from the leading SSH `string` in a key blob: a `u32`
+/// big-endian length followed by that many bytes.
+fn embedded_key_type(blob: &[u8]) -> Result<&[u8], ParseError> {
+    let (len, rest) = match blob {
+        [a, b, c, d, rest @ ..] => (u32::from_be_bytes([*a, *b, *c, *d]), rest),
+        _ => return Err(ParseError::MalformedKeyBlob),
+    };
+    let len = usize::try_from(len).map_err(|_| ParseError::MalformedKeyBlob)?;
+    rest.get(..len).ok_or(ParseError::MalformedKeyBlob)
+}
+
+/// Split off the next whitespace-delimited field, returning the field and the
+/// remainder of the line with leading whitespace trimmed.
+fn split_field(line: &str) -> (&str, &str) {
+    match line.find(char::is_whitespace) {
+        Some(idx) => (&line[..idx], line[idx..].trim_start()),
+        None => (line, ""),
+    }
+}
+
+/// Split off an `authorized_keys` options field, which ends at the first
+/// whitespace outside of a double-quoted string.
+fn split_options(line: &str) -> (&str, &str) {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (idx, ch) in line.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ch if ch.is_whitespace() && !in_quotes => {
+                return (&line[..idx], line[idx..].trim_start());
+            }
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_options, ParseError, PublicKey};
+
+    const ED25519: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAICLh+EIRVGe3pqElKij0MnvlZtxv7uwi3dFKTcrK7WJV";
+
+    #[test]
+    fn options_with_quoted_whitespace() {
+        let line = r#"command="echo \"a b\"",no-pty ssh-ed25519 AAAA"#;
+        assert_eq!(
+            split_options(line),
+            (r#"command="echo \"a b\"",no-pty"#, "ssh-ed25519 AAAA")
+        );
+    }
+
+    #[test]
+    fn authorized_keys_without_options() {
+        let key = PublicKey::parse_authorized_keys(ED25519).unwrap();
+        assert_eq!(key.key_type(), "ssh-ed25519");
+        assert_eq!(key.comment(), None);
+    }
+
+    #[test]
+    fn authorized_keys_reports_bare_key_error() {
+        assert_eq!(
+            PublicKey::parse_authorized_keys("ssh-ed25519 !!!!"),
+            Err(ParseError::InvalidBase64)
+        );
+        assert_eq!(
+            PublicKey::parse_authorized_keys("ssh-ed25519"),
+            Err(ParseError::MissingKeyBlob)
+        );
+    }
+
+    #[test]
+    fn known_hosts_missing_key() {
+        assert_eq!(
+            PublicKey::parse_known_hosts("example.com"),
+            Err(ParseError::MissingKeyType)
+        );
+        assert_eq!(
+            PublicKey::parse_known_hosts("example.com ssh-ed25519"),
+            Err(ParseError::MissingKeyBlob)
+        );
+    }
+}
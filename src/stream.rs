@@ -0,0 +1,189 @@
+//! Encode input that arrives in pieces.
+
+use alloc::string::String;
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::encode;
+
+/// An incremental encoder for input that arrives in pieces, like blocks read
+/// from a file.
+///
+/// Each call to [`update`](Self::update) appends the encoding of as many
+/// complete pairs of input bytes as are available, carrying the checksum and
+/// any odd trailing byte into the next call. [`finish`](Self::finish)
+/// appends the final partial tuple and trailer. The concatenated output is
+/// identical to [`encode`] of the concatenated input, however it is split.
+///
+/// Reusing one output [`String`] across calls bounds memory use by the size
+/// of a single piece rather than the whole input.
+///
+/// The running checksum and any carried byte are derived from the input, so
+/// `Encoder` does not implement [`Copy`] and its [`Debug`](fmt::Debug) output
+/// does not include them. With the **zeroize** feature, `Encoder` implements
+/// `Zeroize` and wipes its state when dropped.
+///
+/// [`encode`]: crate::encode()
+///
+/// # Examples
+///
+/// ```
+/// let mut encoder = boba::Encoder::new();
+/// let mut encoded = String::new();
+/// encoder.update(&mut encoded, "Pine");
+/// assert_eq!(encoded, "xigak-nyryk-h");
+/// encoder.update(&mut encoded, "app");
+/// encoder.update(&mut encoded, "le");
+/// encoder.finish(&mut encoded);
+/// assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+/// ```
+// The state is derived from the input, so it is not implicitly copied.
+#[allow(missing_copy_implementations)]
+#[derive(Clone)]
+pub struct Encoder {
+    checksum: u8,
+    /// The first byte of a pair that was split across calls to `update`.
+    left: Option<u8>,
+    /// Whether the header has been written.
+    started: bool,
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encoder").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl Zeroize for Encoder {
+    fn zeroize(&mut self) {
+        self.checksum.zeroize();
+        self.left.zeroize();
+        self.started.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl ZeroizeOnDrop for Encoder {}
+
+impl Encoder {
+    /// Construct a new `Encoder` at the start of an input.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            checksum: 1,
+            left: None,
+            started: false,
+        }
+    }
+
+    /// Encode the next piece of input, appending the encoded tuples to
+    /// `encoded`.
+    ///
+    /// The header is written by the first call to `update` or
+    /// [`finish`](Self::finish).
+    pub fn update<T: AsRef<[u8]>>(&mut self, encoded: &mut String, data: T) {
+        let mut data = data.as_ref();
+        encoded.reserve(data.len() / 2 * 6 + 7);
+        self.start(encoded);
+        if let (Some(left), [right, rest @ ..]) = (self.left, data) {
+            self.checksum = encode::push_tuples(&[left, *right], self.checksum, encoded);
+            self.left = None;
+            data = rest;
+        }
+        self.checksum = encode::push_tuples(data, self.checksum, encoded);
+        if data.len() % 2 == 1 {
+            self.left = data.last().copied();
+        }
+    }
+
+    /// Finish the encoding, appending the final partial tuple and trailer to
+    /// `encoded`.
+    pub fn finish(mut self, encoded: &mut String) {
+        self.start(encoded);
+        match self.left {
+            Some(byte) => encode::push_tail(&[byte], self.checksum, encoded),
+            None => encode::push_tail(&[], self.checksum, encoded),
+        }
+    }
+
+    fn start(&mut self, encoded: &mut String) {
+        if !self.started {
+            encoded.push(char::from(encode::HEADER));
+            self.started = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::Encoder;
+    use crate::encode;
+
+    #[test]
+    fn matches_encode_for_every_split() {
+        let data = (0..=255_u8).collect::<Vec<_>>();
+        for len in [0, 1, 2, 9, 10, 33] {
+            let data = &data[..len];
+            let expected = encode(data);
+            for piece in 1..=len.max(1) {
+                let mut stream = Encoder::new();
+                let mut encoded = String::new();
+                for chunk in data.chunks(piece) {
+                    stream.update(&mut encoded, chunk);
+                    stream.update(&mut encoded, []);
+                }
+                stream.finish(&mut encoded);
+                assert_eq!(encoded, expected, "len {} in pieces of {}", len, piece);
+            }
+        }
+    }
+
+    #[test]
+    fn finish_without_update() {
+        let mut encoded = String::new();
+        Encoder::new().finish(&mut encoded);
+        assert_eq!(encoded, "xexax");
+    }
+
+    #[test]
+    fn debug_does_not_leak_state() {
+        let mut stream = Encoder::new();
+        stream.update(&mut String::new(), "Pineapple");
+        assert_eq!(alloc::format!("{:?}", stream), "Encoder { .. }");
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_wipes_state() {
+        use zeroize::Zeroize;
+
+        let mut stream = Encoder::new();
+        stream.update(&mut String::new(), "Pineapple");
+        assert_eq!(stream.left, Some(b'e'));
+        stream.zeroize();
+        assert_eq!(stream.checksum, 0);
+        assert_eq!(stream.left, None);
+        assert!(!stream.started);
+    }
+}
@@ -0,0 +1,599 @@
+//! Convert between hex, base64, and Bubble Babble.
+//!
+//! Digests are often logged as hex or base64 but shown to people as Bubble
+//! Babble. [`transcode`] decodes input in one [`Format`] and re-encodes the
+//! bytes in another, one block at a time, without collecting the decoded
+//! bytes. [`transcode_with`] passes each encoded block to a callback so large
+//! inputs can be written out as they are converted.
+//!
+//! # Examples
+//!
+//! ```
+//! # use boba::transcode::{transcode, Format, TranscodeError};
+//! # fn example() -> Result<(), TranscodeError> {
+//! let encoded = transcode("50696e656170706c65", Format::Hex, Format::BubbleBabble)?;
+//! assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+//!
+//! let encoded = transcode(encoded, Format::BubbleBabble, Format::Base64)?;
+//! assert_eq!(encoded, "UGluZWFwcGxl");
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine as _;
+
+use crate::decode::{self, Sink};
+use crate::{DecodeError, Encoder};
+
+/// The number of decoded bytes encoded at a time. A multiple of 6 so every
+/// block but the last encodes to whole base64 quads and Bubble Babble tuples.
+const BLOCK_LEN: usize = 85 * 6;
+
+/// The number of base64 characters decoded at a time. They decode to at most
+/// [`BLOCK_LEN`] bytes.
+const BASE64_BLOCK_LEN: usize = BLOCK_LEN / 3 * 4;
+
+/// Standard base64 that writes padding and accepts input with or without it.
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(true)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// URL-safe base64 that omits padding and accepts input with or without it.
+const BASE64_URL: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Errors from decoding input with [`Format::decode`] and [`transcode`].
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # use boba::transcode::{Format, TranscodeError};
+/// assert_eq!(Format::Hex.decode("abc"), Err(TranscodeError::InvalidHex));
+/// assert_eq!(Format::Base64.decode("!!!!"), Err(TranscodeError::InvalidBase64));
+/// assert_eq!(
+///     Format::BubbleBabble.decode("xexa"),
+///     Err(TranscodeError::InvalidBubbleBabble(DecodeError::MalformedTrailer))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TranscodeError {
+    /// The input was not valid base64 in the expected alphabet.
+    InvalidBase64,
+    /// The input failed to decode as Bubble Babble.
+    InvalidBubbleBabble(DecodeError),
+    /// The input had an odd number of digits or a non-hex character.
+    InvalidHex,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TranscodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidBubbleBabble(err) => Some(err),
+            Self::InvalidBase64 | Self::InvalidHex => None,
+        }
+    }
+}
+
+impl fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBase64 => f.write_str("Input is not valid base64"),
+            Self::InvalidBubbleBabble(err) => {
+                write!(f, "Input is not valid Bubble Babble: {}", err)
+            }
+            Self::InvalidHex => f.write_str("Input is not valid hex"),
+        }
+    }
+}
+
+impl From<DecodeError> for TranscodeError {
+    fn from(err: DecodeError) -> Self {
+        Self::InvalidBubbleBabble(err)
+    }
+}
+
+/// A text encoding of binary data.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Format {
+    /// Hexadecimal digits. Encoding writes lowercase digits and decoding
+    /// accepts either case.
+    Hex,
+    /// Base64 with the standard alphabet from [RFC 4648 section 4]. Encoding
+    /// writes padding and decoding accepts input with or without it.
+    ///
+    /// [RFC 4648 section 4]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+    Base64,
+    /// Base64 with the URL and filename safe alphabet from
+    /// [RFC 4648 section 5]. Encoding omits padding and decoding accepts input
+    /// with or without it.
+    ///
+    /// [RFC 4648 section 5]: https://datatracker.ietf.org/doc/html/rfc4648#section-5
+    Base64Url,
+    /// The Bubble Babble encoding, as implemented by
+    /// [`boba::encode`](crate::encode()) and
+    /// [`boba::decode`](crate::decode()).
+    BubbleBabble,
+}
+
+impl Format {
+    /// Encode `data` in this format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boba::transcode::Format;
+    /// assert_eq!(Format::Hex.encode([0xfb, 0xff]), "fbff");
+    /// assert_eq!(Format::Base64.encode([0xfb, 0xff]), "+/8=");
+    /// assert_eq!(Format::Base64Url.encode([0xfb, 0xff]), "-_8");
+    /// assert_eq!(Format::BubbleBabble.encode([0xfb, 0xff]), "xuvoz-zexax");
+    /// ```
+    #[must_use]
+    pub fn encode<T: AsRef<[u8]>>(self, data: T) -> String {
+        let data = data.as_ref();
+        match self {
+            Self::Hex => {
+                let mut encoded = String::with_capacity(data.len() * 2);
+                encode_hex_into(data, &mut encoded);
+                encoded
+            }
+            Self::Base64 => BASE64.encode(data),
+            Self::Base64Url => BASE64_URL.encode(data),
+            Self::BubbleBabble => crate::encode(data),
+        }
+    }
+
+    /// Decode `encoded` from this format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boba::transcode::{Format, TranscodeError};
+    /// # fn example() -> Result<(), TranscodeError> {
+    /// assert_eq!(Format::Hex.decode("FBff")?, [0xfb, 0xff]);
+    /// assert_eq!(Format::Base64.decode("+/8")?, [0xfb, 0xff]);
+    /// assert_eq!(Format::Base64Url.decode("-_8=")?, [0xfb, 0xff]);
+    /// assert_eq!(Format::BubbleBabble.decode("xuvoz-zexax")?, [0xfb, 0xff]);
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `encoded` is not valid in this format, a [`TranscodeError`] is
+    /// returned.
+    pub fn decode<T: AsRef<[u8]>>(self, encoded: T) -> Result<Vec<u8>, TranscodeError> {
+        let encoded = encoded.as_ref();
+        match self {
+            Self::Hex => {
+                let mut decoded = Vec::with_capacity(encoded.len() / 2);
+                self.decode_with(encoded, &mut decoded)?;
+                Ok(decoded)
+            }
+            Self::Base64 => BASE64
+                .decode(encoded)
+                .map_err(|_| TranscodeError::InvalidBase64),
+            Self::Base64Url => BASE64_URL
+                .decode(encoded)
+                .map_err(|_| TranscodeError::InvalidBase64),
+            Self::BubbleBabble => crate::decode(encoded).map_err(TranscodeError::from),
+        }
+    }
+
+    /// Check that `encoded` is valid in this format without collecting the
+    /// decoded bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boba::DecodeError;
+    /// # use boba::transcode::{Format, TranscodeError};
+    /// assert_eq!(Format::Hex.validate("FBff"), Ok(()));
+    /// assert_eq!(Format::Base64.validate("!!!!"), Err(TranscodeError::InvalidBase64));
+    /// assert_eq!(
+    ///     Format::BubbleBabble.validate("xexa"),
+    ///     Err(TranscodeError::InvalidBubbleBabble(DecodeError::MalformedTrailer))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `encoded` is not valid in this format, the [`TranscodeError`] that
+    /// [`Format::decode`] would return is returned.
+    pub fn validate<T: AsRef<[u8]>>(self, encoded: T) -> Result<(), TranscodeError> {
+        self.decode_with(encoded.as_ref(), &mut Discard)
+    }
+
+    /// Decode `encoded` from this format, passing each decoded byte to `sink`.
+    fn decode_with<S: Sink>(self, encoded: &[u8], sink: &mut S) -> Result<(), TranscodeError> {
+        match self {
+            Self::Hex => decode_hex_with(encoded, sink),
+            Self::Base64 => decode_base64_with(&BASE64, encoded, sink),
+            Self::Base64Url => decode_base64_with(&BASE64_URL, encoded, sink),
+            Self::BubbleBabble => decode::inner_with(encoded, sink).map_err(TranscodeError::from),
+        }
+    }
+}
+
+/// Decode `input` from the `from` format and encode the bytes in the `to`
+/// format.
+///
+/// The decoded bytes are re-encoded one block at a time as they are decoded,
+/// so they are never collected in memory.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::transcode::{transcode, Format, TranscodeError};
+/// # fn example() -> Result<(), TranscodeError> {
+/// let encoded = transcode("xesef-disof-gytuf-katof-movif-baxux", Format::BubbleBabble, Format::Hex)?;
+/// assert_eq!(encoded, "31323334353637383930");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `input` is not valid in the `from` format, a [`TranscodeError`] is
+/// returned.
+pub fn transcode<T: AsRef<[u8]>>(
+    input: T,
+    from: Format,
+    to: Format,
+) -> Result<String, TranscodeError> {
+    let mut transcoded = String::new();
+    transcode_with(input, from, to, |encoded| {
+        transcoded.push_str(encoded);
+        Ok::<_, TranscodeError>(())
+    })?;
+    Ok(transcoded)
+}
+
+/// Decode `input` from the `from` format and encode the bytes in the `to`
+/// format, passing the encoding to `write` one block at a time.
+///
+/// Concatenating the blocks passed to `write` gives the same encoding as
+/// [`transcode`]. Once `write` returns an error it is not called again and
+/// the error is returned.
+///
+/// Errors in `input` may be found after some blocks have been written. Call
+/// [`Format::validate`] first to reject invalid input before writing
+/// anything.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::transcode::{transcode_with, Format, TranscodeError};
+/// # fn example() -> Result<(), TranscodeError> {
+/// let input = "xesef-disof-gytuf-katof-movif-baxux";
+/// Format::BubbleBabble.validate(input)?;
+/// let mut blocks = Vec::new();
+/// transcode_with(input, Format::BubbleBabble, Format::Hex, |block| {
+///     blocks.push(block.to_owned());
+///     Ok::<_, TranscodeError>(())
+/// })?;
+/// assert_eq!(blocks.concat(), "31323334353637383930");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `input` is not valid in the `from` format, the [`TranscodeError`] is
+/// converted into `E` and returned. If `write` returns an error, that error
+/// is returned.
+pub fn transcode_with<T, F, E>(input: T, from: Format, to: Format, write: F) -> Result<(), E>
+where
+    T: AsRef<[u8]>,
+    F: FnMut(&str) -> Result<(), E>,
+    E: From<TranscodeError>,
+{
+    let mut transcoder = Transcoder::new(to, write);
+    from.decode_with(input.as_ref(), &mut transcoder)?;
+    transcoder.finish()
+}
+
+/// Encodes decoded bytes in a [`Format`] one block at a time and passes the
+/// encoding of each block to `write`.
+struct Transcoder<F, E> {
+    to: Format,
+    write: F,
+    error: Option<E>,
+    stream: Encoder,
+    encoded: String,
+    block: [u8; BLOCK_LEN],
+    len: usize,
+}
+
+impl<F, E> Transcoder<F, E>
+where
+    F: FnMut(&str) -> Result<(), E>,
+{
+    fn new(to: Format, write: F) -> Self {
+        Self {
+            to,
+            write,
+            error: None,
+            stream: Encoder::new(),
+            encoded: String::new(),
+            block: [0; BLOCK_LEN],
+            len: 0,
+        }
+    }
+
+    fn flush(&mut self) {
+        let block = &self.block[..self.len];
+        self.len = 0;
+        match self.to {
+            Format::Hex => encode_hex_into(block, &mut self.encoded),
+            Format::Base64 => BASE64.encode_string(block, &mut self.encoded),
+            Format::Base64Url => BASE64_URL.encode_string(block, &mut self.encoded),
+            Format::BubbleBabble => self.stream.update(&mut self.encoded, block),
+        }
+        self.write_encoded();
+    }
+
+    fn write_encoded(&mut self) {
+        if self.error.is_none() && !self.encoded.is_empty() {
+            if let Err(err) = (self.write)(&self.encoded) {
+                self.error = Some(err);
+            }
+        }
+        self.encoded.clear();
+    }
+
+    fn finish(mut self) -> Result<(), E> {
+        self.flush();
+        if let Format::BubbleBabble = self.to {
+            mem::take(&mut self.stream).finish(&mut self.encoded);
+            self.write_encoded();
+        }
+        self.error.map_or(Ok(()), Err)
+    }
+}
+
+impl<F, E> Sink for Transcoder<F, E>
+where
+    F: FnMut(&str) -> Result<(), E>,
+{
+    #[inline]
+    fn push_byte(&mut self, byte: u8) {
+        if self.len == BLOCK_LEN {
+            self.flush();
+        }
+        self.block[self.len] = byte;
+        self.len += 1;
+    }
+}
+
+/// A sink that drops decoded bytes, for validating input.
+struct Discard;
+
+impl Sink for Discard {
+    #[inline]
+    fn push_byte(&mut self, _: u8) {}
+}
+
+fn encode_hex_into(data: &[u8], encoded: &mut String) {
+    encoded.reserve(data.len() * 2);
+    for &byte in data {
+        encoded.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+        encoded.push(char::from(HEX_DIGITS[usize::from(byte & 0x0F)]));
+    }
+}
+
+fn decode_hex_with<S: Sink>(encoded: &[u8], sink: &mut S) -> Result<(), TranscodeError> {
+    fn digit(byte: u8) -> Result<u8, TranscodeError> {
+        match byte {
+            b'0'..=b'9' => Ok(byte - b'0'),
+            b'a'..=b'f' => Ok(byte - b'a' + 10),
+            b'A'..=b'F' => Ok(byte - b'A' + 10),
+            _ => Err(TranscodeError::InvalidHex),
+        }
+    }
+
+    if encoded.len() % 2 != 0 {
+        return Err(TranscodeError::InvalidHex);
+    }
+    for pair in encoded.chunks_exact(2) {
+        sink.push_byte(digit(pair[0])? << 4 | digit(pair[1])?);
+    }
+    Ok(())
+}
+
+fn decode_base64_with<S: Sink>(
+    engine: &GeneralPurpose,
+    encoded: &[u8],
+    sink: &mut S,
+) -> Result<(), TranscodeError> {
+    let mut block = [0; BLOCK_LEN];
+    let mut chunks = encoded.chunks(BASE64_BLOCK_LEN).peekable();
+    while let Some(chunk) = chunks.next() {
+        // Padding may only end the input. Each chunk is decoded on its own,
+        // so padding at the end of a chunk with more input after it has to
+        // be rejected here.
+        if chunks.peek().is_some() && chunk.last() == Some(&b'=') {
+            return Err(TranscodeError::InvalidBase64);
+        }
+        let len = engine
+            .decode_slice(chunk, &mut block)
+            .map_err(|_| TranscodeError::InvalidBase64)?;
+        for &byte in &block[..len] {
+            sink.push_byte(byte);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{transcode, transcode_with, Format, TranscodeError};
+    use crate::DecodeError;
+
+    const FORMATS: [Format; 4] = [
+        Format::Hex,
+        Format::Base64,
+        Format::Base64Url,
+        Format::BubbleBabble,
+    ];
+
+    #[test]
+    fn round_trip_every_direction() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        for len in [0, 1, 2, 3, 31, 32, 256] {
+            let data = &data[..len];
+            for &from in &FORMATS {
+                for &to in &FORMATS {
+                    let encoded = transcode(from.encode(data), from, to).unwrap();
+                    assert_eq!(to.decode(encoded).unwrap(), data, "{:?} -> {:?}", from, to);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sha256_hex_to_bubble_babble() {
+        let digest = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let encoded = transcode(digest, Format::Hex, Format::BubbleBabble).unwrap();
+        assert_eq!(
+            transcode(encoded, Format::BubbleBabble, Format::Hex).unwrap(),
+            digest
+        );
+    }
+
+    #[test]
+    fn hex_errors() {
+        assert_eq!(Format::Hex.decode("0"), Err(TranscodeError::InvalidHex));
+        assert_eq!(Format::Hex.decode("0g"), Err(TranscodeError::InvalidHex));
+        assert_eq!(Format::Hex.decode(" 00"), Err(TranscodeError::InvalidHex));
+        assert_eq!(Format::Hex.decode(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn base64_alphabets_are_distinct() {
+        assert_eq!(
+            Format::Base64.decode("-_8"),
+            Err(TranscodeError::InvalidBase64)
+        );
+        assert_eq!(
+            Format::Base64Url.decode("+/8="),
+            Err(TranscodeError::InvalidBase64)
+        );
+    }
+
+    #[test]
+    fn transcode_spanning_blocks() {
+        let data = (0..=255_u8)
+            .cycle()
+            .step_by(7)
+            .take(4000)
+            .collect::<Vec<u8>>();
+        for len in [509, 510, 511, 1020, 1021, 4000] {
+            let data = &data[..len];
+            for &from in &FORMATS {
+                for &to in &FORMATS {
+                    let encoded = transcode(from.encode(data), from, to).unwrap();
+                    assert_eq!(encoded, to.encode(data), "{:?} -> {:?}", from, to);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn base64_padding_only_at_end() {
+        for format in [Format::Base64, Format::Base64Url] {
+            let mut encoded = "AAAA".repeat(super::BASE64_BLOCK_LEN / 4 - 1);
+            encoded.push_str("AA==AAAA");
+            assert_eq!(format.decode(&encoded), Err(TranscodeError::InvalidBase64));
+            assert_eq!(
+                format.validate(&encoded),
+                Err(TranscodeError::InvalidBase64)
+            );
+            assert_eq!(
+                transcode(&encoded, format, Format::Hex),
+                Err(TranscodeError::InvalidBase64)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_matches_decode() {
+        let inputs = [
+            "",
+            "0",
+            "0g",
+            "fbff",
+            "+/8",
+            "-_8=",
+            "xexax",
+            "xuvoz-zexax",
+            "xexa",
+        ];
+        for &format in &FORMATS {
+            for input in inputs {
+                assert_eq!(
+                    format.validate(input),
+                    format.decode(input).map(drop),
+                    "{:?} {:?}",
+                    format,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn transcode_with_stops_at_write_error() {
+        let data = [0; 2000];
+        let mut calls = 0;
+        let result = transcode_with(
+            Format::Hex.encode(data),
+            Format::Hex,
+            Format::BubbleBabble,
+            |_| {
+                calls += 1;
+                Err(TranscodeError::InvalidHex)
+            },
+        );
+        assert_eq!(result, Err(TranscodeError::InvalidHex));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn bubble_babble_error() {
+        assert_eq!(
+            transcode(
+                "xigak-nyryk-Humil-bosek-sonax",
+                Format::BubbleBabble,
+                Format::Hex
+            ),
+            Err(TranscodeError::InvalidBubbleBabble(
+                DecodeError::InvalidByte(12)
+            ))
+        );
+    }
+}
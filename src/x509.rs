@@ -0,0 +1,254 @@
+//! Bubble Babble fingerprints of X.509 certificates.
+//!
+//! Certificates are pinned by the SHA-256 digest of either the whole
+//! DER-encoded certificate or its DER-encoded `SubjectPublicKeyInfo`. The
+//! latter survives certificate renewals that reuse the same key pair.
+//!
+//! Functions in this module accept certificates in either PEM or DER form.
+//! Inputs that begin with a `-----BEGIN` encapsulation boundary are treated as
+//! PEM.
+//!
+//! # Examples
+//!
+//! ```
+//! # use boba::x509::{self, ParseError};
+//! # fn example() -> Result<(), ParseError> {
+//! # let pem = include_str!("../tests/fixtures/x509/cert.pem");
+//! let fingerprint = x509::certificate_fingerprint(pem)?;
+//! assert_eq!(
+//!     fingerprint.to_string(),
+//!     "xopat-filam-noceb-lutub-ditam-napuk-fokid-kasip-hysag-funom-ligyz-lysut-docuf-fibuv-lykez-lycag-duxyx"
+//! );
+//!
+//! // Read the first 8 bytes of the digest aloud.
+//! assert_eq!(fingerprint.encode_truncated(8), "xopat-filam-noceb-lutub-dexax");
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use sha2::{Digest, Sha256};
+use x509_cert::der::{pem, Decode, Encode};
+use x509_cert::Certificate;
+
+const PEM_BOUNDARY: &[u8] = b"-----BEGIN";
+const PEM_LABEL: &str = "CERTIFICATE";
+
+/// Parsing errors from the fingerprint functions in [`boba::x509`](self).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::x509::{self, ParseError};
+/// assert_eq!(x509::certificate_fingerprint([0x30, 0x00]), Err(ParseError::InvalidDer));
+/// assert_eq!(
+///     x509::certificate_fingerprint("-----BEGIN CERTIFICATE-----"),
+///     Err(ParseError::InvalidPem)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseError {
+    /// The input was not a DER-encoded X.509 certificate.
+    InvalidDer,
+    /// The input was not a PEM-encoded X.509 certificate.
+    InvalidPem,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDer => f.write_str("Invalid DER-encoded certificate"),
+            Self::InvalidPem => f.write_str("Invalid PEM-encoded certificate"),
+        }
+    }
+}
+
+/// A SHA-256 fingerprint of a certificate or public key.
+///
+/// The [`Display`](fmt::Display) implementation writes the Bubble Babble
+/// encoding of the full digest.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fingerprint([u8; 32]);
+
+impl Fingerprint {
+    /// The raw SHA-256 digest.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Encode the full SHA-256 digest with [`boba::encode`](crate::encode()).
+    #[must_use]
+    pub fn encode(&self) -> String {
+        crate::encode(self.0)
+    }
+
+    /// Encode the leading `len` bytes of the SHA-256 digest with
+    /// [`boba::encode`](crate::encode()).
+    ///
+    /// Truncated fingerprints are shorter to read aloud when comparing pins
+    /// verbally. The result is a well-formed Bubble Babble encoding of the
+    /// digest prefix, not a prefix of the full encoding. `len` is clamped to
+    /// the 32 byte digest length.
+    #[must_use]
+    pub fn encode_truncated(&self, len: usize) -> String {
+        let len = len.min(self.0.len());
+        crate::encode(&self.0[..len])
+    }
+}
+
+impl AsRef<[u8]> for Fingerprint {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+/// Compute the SHA-256 fingerprint of a PEM- or DER-encoded certificate.
+///
+/// The digest covers the entire DER encoding of the certificate.
+///
+/// # Errors
+///
+/// If the input is not a well-formed X.509 certificate, a [`ParseError`] is
+/// returned.
+pub fn certificate_fingerprint<T: AsRef<[u8]>>(cert: T) -> Result<Fingerprint, ParseError> {
+    let der = to_der(cert.as_ref())?;
+    // Parse the certificate to reject inputs that are not certificates.
+    Certificate::from_der(&der).map_err(|_| ParseError::InvalidDer)?;
+    Ok(digest(&der))
+}
+
+/// Compute the SHA-256 fingerprint of the `SubjectPublicKeyInfo` of a PEM- or
+/// DER-encoded certificate.
+///
+/// This is the digest used for HTTP public key pinning.
+///
+/// ```
+/// # use boba::x509::{self, ParseError};
+/// # fn example() -> Result<(), ParseError> {
+/// # let der = include_bytes!("../tests/fixtures/x509/cert.der");
+/// let fingerprint = x509::spki_fingerprint(der)?;
+/// assert_eq!(
+///     fingerprint.to_string(),
+///     "xokim-hapez-disih-tymeb-gefub-gadan-vivyk-mutyl-bigok-cokuv-dylez-dedak-zamob-bibub-suhob-dihev-vyxex"
+/// );
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the input is not a well-formed X.509 certificate, a [`ParseError`] is
+/// returned.
+pub fn spki_fingerprint<T: AsRef<[u8]>>(cert: T) -> Result<Fingerprint, ParseError> {
+    let der = to_der(cert.as_ref())?;
+    let cert = Certificate::from_der(&der).map_err(|_| ParseError::InvalidDer)?;
+    let spki = cert
+        .tbs_certificate
+        .subject_public_key_info
+        .to_der()
+        .map_err(|_| ParseError::InvalidDer)?;
+    Ok(digest(&spki))
+}
+
+fn to_der(cert: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let trimmed = cert
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .map_or(&[][..], |start| &cert[start..]);
+    if !trimmed.starts_with(PEM_BOUNDARY) {
+        return Ok(cert.to_vec());
+    }
+    match pem::decode_vec(trimmed) {
+        Ok((PEM_LABEL, der)) => Ok(der),
+        _ => Err(ParseError::InvalidPem),
+    }
+}
+
+fn digest(der: &[u8]) -> Fingerprint {
+    Fingerprint(Sha256::digest(der).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{certificate_fingerprint, spki_fingerprint, ParseError};
+
+    const PEM: &str = include_str!("../tests/fixtures/x509/cert.pem");
+    const DER: &[u8] = include_bytes!("../tests/fixtures/x509/cert.der");
+
+    // Captured with:
+    //
+    // ```sh
+    // openssl x509 -in cert.pem -noout -fingerprint -sha256
+    // ```
+    const CERT_SHA256: [u8; 32] = [
+        0xA8, 0xD3, 0x9E, 0x89, 0x86, 0x07, 0xB5, 0x02, 0x74, 0x89, 0x29, 0x63, 0x59, 0x26, 0x70,
+        0xA5, 0xF2, 0x43, 0xE4, 0x87, 0x11, 0xF7, 0x70, 0xD2, 0xC5, 0x33, 0x03, 0xE7, 0x59, 0xF7,
+        0xC6, 0x42,
+    ];
+
+    // Captured with:
+    //
+    // ```sh
+    // openssl x509 -in cert.pem -noout -pubkey | openssl pkey -pubin -outform DER | openssl dgst -sha256
+    // ```
+    const SPKI_SHA256: [u8; 32] = [
+        0x9a, 0x85, 0xab, 0xf2, 0x73, 0x5d, 0xa0, 0x04, 0x8e, 0x04, 0xc8, 0x9e, 0x79, 0x68, 0xb4,
+        0x70, 0x10, 0x61, 0x19, 0xe2, 0xdd, 0xf2, 0x89, 0x6f, 0xe0, 0x00, 0xc2, 0x0c, 0x55, 0x02,
+        0x97, 0xee,
+    ];
+
+    #[test]
+    fn certificate_pem_and_der() {
+        let from_pem = certificate_fingerprint(PEM).unwrap();
+        let from_der = certificate_fingerprint(DER).unwrap();
+        assert_eq!(from_pem.as_bytes(), &CERT_SHA256);
+        assert_eq!(from_der, from_pem);
+    }
+
+    #[test]
+    fn spki_pem_and_der() {
+        let from_pem = spki_fingerprint(PEM).unwrap();
+        let from_der = spki_fingerprint(DER).unwrap();
+        assert_eq!(from_pem.as_bytes(), &SPKI_SHA256);
+        assert_eq!(from_der, from_pem);
+    }
+
+    #[test]
+    fn truncated() {
+        let fingerprint = spki_fingerprint(PEM).unwrap();
+        assert_eq!(
+            fingerprint.encode_truncated(8),
+            "xokim-hapez-disih-tymeb-gyxix"
+        );
+        assert_eq!(fingerprint.encode_truncated(0), "xexax");
+        assert_eq!(fingerprint.encode_truncated(100), fingerprint.encode());
+    }
+
+    #[test]
+    fn rejects_other_pem_labels() {
+        let key = PEM.replace("CERTIFICATE", "PUBLIC KEY");
+        assert_eq!(certificate_fingerprint(key), Err(ParseError::InvalidPem));
+    }
+
+    #[test]
+    fn rejects_trailing_data() {
+        let mut der = DER.to_vec();
+        der.push(0);
+        assert_eq!(certificate_fingerprint(der), Err(ParseError::InvalidDer));
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(all(feature = "serde", feature = "alloc"))]
+
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Fingerprints {
+    #[serde(with = "boba::serde")]
+    vec: Vec<u8>,
+    #[serde(with = "boba::serde")]
+    array: [u8; 4],
+    #[serde(with = "boba::serde::option")]
+    some: Option<Vec<u8>>,
+    #[serde(with = "boba::serde::option")]
+    none: Option<[u8; 2]>,
+}
+
+fn fingerprints() -> Fingerprints {
+    Fingerprints {
+        vec: b"Pineapple".to_vec(),
+        array: *b"boba",
+        some: Some(b"1234567890".to_vec()),
+        none: None,
+    }
+}
+
+#[test]
+fn human_readable() {
+    let json = serde_json::to_value(fingerprints()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "vec": "xigak-nyryk-humil-bosek-sonax",
+            "array": "ximik-zymok-cyxax",
+            "some": "xesef-disof-gytuf-katof-movif-baxux",
+            "none": null,
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<Fingerprints>(json).unwrap(),
+        fingerprints()
+    );
+}
+
+#[test]
+fn binary() {
+    let bytes = bincode::serialize(&fingerprints()).unwrap();
+    // Raw bytes are serialized, not the Bubble Babble string.
+    assert!(bytes.windows(9).any(|window| window == b"Pineapple"));
+    assert_eq!(
+        bincode::deserialize::<Fingerprints>(&bytes).unwrap(),
+        fingerprints()
+    );
+}
+
+#[test]
+fn decode_error_has_position() {
+    let json = serde_json::json!({
+        "vec": "xigak-nyryk-Humil-bosek-sonax",
+        "array": "ximik-zymok-cyxax",
+        "some": null,
+        "none": null,
+    });
+    let err = serde_json::from_value::<Fingerprints>(json).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid Bubble Babble encoding: Encountered byte outside of encoding alphabet at position 12"
+    );
+}
+
+#[test]
+fn array_length_mismatch() {
+    let json = serde_json::json!({
+        "vec": "xexax",
+        "array": "xigak-nyryk-humil-bosek-sonax",
+        "some": null,
+        "none": null,
+    });
+    let err = serde_json::from_value::<Fingerprints>(json).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 9, expected bytes of the expected length"
+    );
+}
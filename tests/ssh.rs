@@ -0,0 +1,82 @@
+#![cfg(feature = "ssh")]
+
+// Test vectors in `tests/fixtures/ssh` were captured with:
+//
+// ```sh
+// ssh-keygen -l -B -f <file> > <file>.bubblebabble
+// ```
+
+use boba::ssh::PublicKey;
+
+/// Extract the fingerprints from `ssh-keygen -l -B` output, which has lines
+/// like `256 xukek-...-poxix alice@example.com (ED25519)`.
+fn expected(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .map(|line| line.split_whitespace().nth(1).unwrap())
+        .collect()
+}
+
+#[test]
+fn public_key_files() {
+    let cases = [
+        (
+            include_str!("fixtures/ssh/id_ed25519.pub"),
+            include_str!("fixtures/ssh/id_ed25519.pub.bubblebabble"),
+        ),
+        (
+            include_str!("fixtures/ssh/id_rsa.pub"),
+            include_str!("fixtures/ssh/id_rsa.pub.bubblebabble"),
+        ),
+        (
+            include_str!("fixtures/ssh/id_ecdsa.pub"),
+            include_str!("fixtures/ssh/id_ecdsa.pub.bubblebabble"),
+        ),
+    ];
+    for (public_key, output) in cases.iter() {
+        let key = PublicKey::parse(public_key).unwrap();
+        assert_eq!(vec![key.fingerprint().as_str()], expected(output));
+    }
+}
+
+#[test]
+fn authorized_keys() {
+    let fingerprints = include_str!("fixtures/ssh/authorized_keys")
+        .lines()
+        .filter_map(|line| PublicKey::parse_authorized_keys(line).ok())
+        .map(|key| key.fingerprint())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fingerprints,
+        expected(include_str!("fixtures/ssh/authorized_keys.bubblebabble"))
+    );
+}
+
+#[test]
+fn known_hosts() {
+    let fingerprints = include_str!("fixtures/ssh/known_hosts")
+        .lines()
+        // `ssh-keygen -l` skips `@cert-authority` and `@revoked` entries.
+        .filter(|line| !line.starts_with('@'))
+        .map(|line| PublicKey::parse_known_hosts(line).unwrap())
+        .map(|key| key.fingerprint())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fingerprints,
+        expected(include_str!("fixtures/ssh/known_hosts.bubblebabble"))
+    );
+}
+
+#[test]
+fn known_hosts_cert_authority() {
+    let line = include_str!("fixtures/ssh/known_hosts")
+        .lines()
+        .find(|line| line.starts_with("@cert-authority"))
+        .unwrap();
+    let key = PublicKey::parse_known_hosts(line).unwrap();
+    assert_eq!(key.key_type(), "ecdsa-sha2-nistp256");
+    assert_eq!(
+        vec![key.fingerprint().as_str()],
+        expected(include_str!("fixtures/ssh/id_ecdsa.pub.bubblebabble"))
+    );
+}